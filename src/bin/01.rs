@@ -1,49 +1,59 @@
-advent_of_code::solution!(1);
+use advent_of_code::{
+    counter::Counter,
+    parse::{self, ParseError},
+};
 
+advent_of_code::solution!(1);
 
-pub fn parse(input: &str) -> (Vec<i32>, Vec<i32>) {
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-    for line in input.lines() {
-        let mut parts = line.split_ascii_whitespace();
-        let l = parts.next().unwrap();
-        let r = parts.next_back().unwrap();
-        left.push(l.parse().unwrap());
-        right.push(r.parse().unwrap());
-    }
+pub fn parse(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
+    let pairs = parse::parse_lines(input, |line| {
+        match parse::int_fields::<i32>(line, line.split_ascii_whitespace())?[..] {
+            [l, r] => Ok((l, r)),
+            _ => Err(ParseError::new("expected two numbers", line)),
+        }
+    })?;
 
-    (left, right)
+    Ok(pairs.into_iter().unzip())
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut left, mut right) = parse(input);
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
+    let (mut left, mut right) = parse(input)?;
     left.sort_unstable();
     right.sort_unstable();
 
-    Some(left.iter().zip(right).map(|(l,r)| (l-r).abs() as u32).sum())
+    Ok(left.iter().zip(right).map(|(l, r)| (l - r).unsigned_abs()).sum())
 }
 
-pub fn part_two(input: &str) -> Option<i32> {
-    let (left, right) = parse(input);
+pub fn part_two(input: &str) -> Result<i32, ParseError> {
+    let (left, right) = parse(input)?;
+
+    let right: Counter<i32> = right.into_iter().collect();
 
-    Some(left.into_iter()
-        .map(|l| l * (right.iter().filter(|r| **r == l).count() as i32))
-        .sum())
+    Ok(left.into_iter().map(|l| l * right.count(&l) as i32).sum())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_error() {
+        let err = parse("3   4\n4   x3\n5\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "x3"));
+
+        let err = parse("3   4\n5\n").unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "5"));
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        assert_eq!(result, Ok(11));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        assert_eq!(result, Ok(31));
     }
 }
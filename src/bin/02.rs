@@ -1,4 +1,4 @@
-use std::ops::{Range, RangeInclusive};
+use advent_of_code::interval::IntervalSet;
 
 advent_of_code::solution!(2);
 
@@ -34,8 +34,7 @@ fn is_valid(arr: &[i32]) -> bool {
         }
     }
 
-    let result = small_diff && (dec || inc);
-    result
+    small_diff && (dec || inc)
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
@@ -43,53 +42,47 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(levers.into_iter().filter(|v| is_valid(v)).count() as u32)
 }
 
-fn can_make_valid(arr: &[i32], diff_range: RangeInclusive<i32>) -> bool {
-    let mut dp = vec![[false, false]; arr.len()];
-    dp[0][0] = true;
-    dp[0][1] = true;
-    dp[1][1] = true;
-
-    if diff_range.contains(&(arr[1] - arr[0])) {
-        dp[1][0] = true;
-    }
-
-    for p in 2..arr.len() {
-        let d1 = arr[p] - arr[p-1];
-        let d2 = arr[p] - arr[p-2];
-
-        if diff_range.contains(&d1) {
-            dp[p][0] = dp[p-1][0];
-            dp[p][1] = dp[p-1][1];
-        }
-
-        if diff_range.contains(&d2) {
-            dp[p][1] |= dp[p-2][0];
-        }
-
-        dp[p][1] |= dp[p][0];
+/// Whether removing at most `k` levels leaves a report whose steps all lie in `diff_range`.
+fn can_make_valid(arr: &[i32], diff_range: &IntervalSet<i32>, k: usize) -> bool {
+    let n = arr.len();
+    // `removals[p]`: fewest levels removed from `arr[..p]` so that the kept ones end with `arr[p]`.
+    // Keeping `arr[q]` right before `arr[p]` removes `p - q - 1` levels, so `q` is one of the
+    // `k + 1` levels before `p`.
+    let mut removals = vec![0; n];
+    for p in 0..n {
+        removals[p] = (p.saturating_sub(k + 1)..p)
+            .filter(|&q| diff_range.contains(arr[p] - arr[q]))
+            .map(|q| removals[q] + p - q - 1)
+            .fold(p, usize::min);
     }
 
-    dp[arr.len()-1][1] || dp[arr.len()-2][0]
+    n == 0 || (0..n).any(|p| removals[p] + n - 1 - p <= k)
 }
 
 #[test]
 fn test_can_valid() {
     let arr = [10, 12, 14, 17, 20, 23, 26, 30];
-    assert!(can_make_valid(&arr, 1..=3));
+    assert!(can_make_valid(&arr, &IntervalSet::from(1..4), 1));
+    assert!(!can_make_valid(&arr, &IntervalSet::from(1..4), 0));
 }
 
 
-fn can_make_safe(arr: &[i32]) -> bool {
-    can_make_valid(arr, 1..=3) || can_make_valid(arr, -3..=-1)
+/// Whether the report is safe after removing at most `k` levels.
+pub fn can_make_safe(arr: &[i32], k: usize) -> bool {
+    can_make_valid(arr, &IntervalSet::from(1..4), k)
+        || can_make_valid(arr, &IntervalSet::from(-3..0), k)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let levers = parse(input);
-    Some(levers.into_iter().filter(|v| can_make_safe(v)).count() as u32)
+    Some(levers.into_iter().filter(|v| can_make_safe(v, 1)).count() as u32)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::testing::XorShift;
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -103,4 +96,50 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4));
     }
+
+    // tries every way to remove at most `k` levels.
+    fn can_make_safe_brute_force(arr: &[i32], k: usize) -> bool {
+        (0..=k.min(arr.len())).any(|removed| {
+            (0..arr.len()).combinations(removed).any(|skip| {
+                let kept: Vec<i32> = (0..arr.len())
+                    .filter(|i| !skip.contains(i))
+                    .map(|i| arr[i])
+                    .collect();
+                is_valid(&kept)
+            })
+        })
+    }
+
+    #[test]
+    fn test_short_reports() {
+        for k in 0..3 {
+            assert!(can_make_safe(&[], k));
+            assert!(can_make_safe(&[5], k));
+        }
+        assert!(!can_make_safe(&[5, 5], 0));
+        assert!(can_make_safe(&[5, 5], 1));
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rng = XorShift::new(2);
+
+        for _ in 0..3000 {
+            let len = rng.below(9) as usize;
+            // small steps, so that most reports are nearly safe.
+            let mut level = 50;
+            let arr: Vec<i32> = (0..len)
+                .map(|_| {
+                    level += rng.below(11) as i32 - 5;
+                    level
+                })
+                .collect();
+            let k = rng.below(4) as usize;
+            assert_eq!(
+                can_make_safe(&arr, k),
+                can_make_safe_brute_force(&arr, k),
+                "{arr:?}, k = {k}"
+            );
+        }
+    }
 }
@@ -1,47 +1,27 @@
 advent_of_code::solution!(3);
 
-use regex::Regex;
+use advent_of_code::scanner::{Instruction, Scanner};
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let re = Regex::new(r"mul\(\d+,\d+\)").unwrap();
-    let mut sum = 0;
-    for mul_op in re.find_iter(input) {
-        let parts = mul_op
-            .as_str()
-            .split(|c| c == '(' || c == ')' || c == ',')
-            .collect::<Vec<_>>();
-
-        if let [_, l, r, ..] = parts[..] {
-            let l: u32 = l.parse().unwrap();
-            let r: u32 = r.parse().unwrap();
-            sum += (l * r);
-        }
-    }
+    let sum = Scanner::new(input)
+        .map(|instruction| match instruction {
+            Instruction::Mul(l, r) => l * r,
+            _ => 0,
+        })
+        .sum();
 
     Some(sum)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let re = Regex::new(r"mul\(\d+,\d+\)|do\(\)|don't\(\)").unwrap();
     let mut sum = 0;
     let mut enabled = true;
-    for group in re.find_iter(input) {
-        let s = group.as_str();
-        match s {
-            "do()" => enabled = true,
-            "don't()" => enabled = false,
-            mul_op if enabled => {
-                let parts = mul_op
-                    .split(|c| c == '(' || c == ')' || c == ',')
-                    .collect::<Vec<_>>();
-
-                if let [_, l, r, ..] = parts[..] {
-                    let l: u32 = l.parse().unwrap();
-                    let r: u32 = r.parse().unwrap();
-                    sum += l * r;
-                }
-            }
-            _ => {}
+    for instruction in Scanner::new(input) {
+        match instruction {
+            Instruction::Do => enabled = true,
+            Instruction::Dont => enabled = false,
+            Instruction::Mul(l, r) if enabled => sum += l * r,
+            Instruction::Mul(..) => {}
         }
     }
 
@@ -50,8 +30,46 @@ pub fn part_two(input: &str) -> Option<u32> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Instant;
+
+    use advent_of_code::testing::XorShift;
+    use regex::Regex;
+
     use super::*;
 
+    // the previous solution, with the operands limited to 1-3 digits.
+    fn part_two_regex(input: &str) -> u32 {
+        let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)").unwrap();
+        let mut sum = 0;
+        let mut enabled = true;
+        for captures in re.captures_iter(input) {
+            match &captures[0] {
+                "do()" => enabled = true,
+                "don't()" => enabled = false,
+                _ if enabled => {
+                    let l: u32 = captures[1].parse().unwrap();
+                    let r: u32 = captures[2].parse().unwrap();
+                    sum += l * r;
+                }
+                _ => {}
+            }
+        }
+        sum
+    }
+
+    // corrupted memory made of instruction fragments, so near misses are common.
+    fn corrupted(seed: u64, len: usize) -> String {
+        const PIECES: [&str; 12] = [
+            "mul(", "do()", "don't()", "do", "m", "1", "23", "4567", ",", ")", "(", "x",
+        ];
+        let mut rng = XorShift::new(seed);
+        let mut input = String::new();
+        while input.len() < len {
+            input.push_str(PIECES[rng.below(PIECES.len() as u64) as usize]);
+        }
+        input
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -63,4 +81,30 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(48));
     }
+
+    #[test]
+    fn test_matches_regex() {
+        for seed in 1..200 {
+            let input = corrupted(seed, 300);
+            assert_eq!(part_two(&input), Some(part_two_regex(&input)), "{input}");
+        }
+    }
+
+    /// Run with `cargo test --release --bin 03 -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_against_regex() {
+        let input = corrupted(42, 10_000_000);
+
+        let timer = Instant::now();
+        let expected = part_two_regex(&input);
+        let regex = timer.elapsed();
+
+        let timer = Instant::now();
+        let result = part_two(&input);
+        let scanner = timer.elapsed();
+
+        assert_eq!(result, Some(expected));
+        println!("regex: {regex:?}, scanner: {scanner:?}");
+    }
 }
@@ -1,103 +1,225 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+};
+
+use advent_of_code::{
+    graph::Cycle,
+    parse::{self, ParseError},
+};
 
 advent_of_code::solution!(5);
 
+/// Pages are two-digit numbers.
+const PAGES: usize = 100;
+
+/// The page ordering rules as a 100×100 bit table.
+#[derive(Debug)]
 struct RuleSet {
-    rules: HashMap<u32, HashSet<u32>>,
+    // bit `b` of `before[a]` is set for a rule `a|b`.
+    before: [u128; PAGES],
+    // bit `a` of `after[b]` is set for a rule `a|b`.
+    after: [u128; PAGES],
 }
 
-impl RuleSet {
-    fn is_valid(&self, from: u32, to: u32) -> bool {
-        !self.rules.get(&to).map_or(false, |h| h.contains(&from))
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            before: [0; PAGES],
+            after: [0; PAGES],
+        }
     }
 }
 
-fn parse(input: &str) -> (RuleSet, Vec<Vec<u32>>) {
-    let mut rules: HashMap<u32, HashSet<u32>> = HashMap::new();
-    input
-        .lines()
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            let mut parts = line.split('|');
-            let from = parts.next().unwrap().parse().unwrap();
-            let to = parts.next().unwrap().parse().unwrap();
-            (from, to)
-        })
-        .for_each(|(f, t)| {
-            rules.entry(f).or_default().insert(t);
-        });
+/// Why the rules don't order the pages of an update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+    /// No rule decides which of the two pages comes first.
+    Unrelated(u8, u8),
+    /// The rules contradict each other.
+    Cycle(Cycle<u8>),
+}
 
-    let ruleset = RuleSet { rules };
+impl Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::Unrelated(a, b) => write!(f, "no rule orders pages {a} and {b}"),
+            OrderError::Cycle(cycle) => write!(f, "{cycle}"),
+        }
+    }
+}
 
-    let pages = input
-        .lines()
-        .skip_while(|l| !l.is_empty())
-        .skip(1)
-        .map(|line| line.split(",").map(|s| s.parse().unwrap()).collect())
-        .collect();
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    /// `update` is the 1-based index of the offending update.
+    Order {
+        update: usize,
+        error: OrderError,
+    },
+}
 
-    (ruleset, pages)
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
 }
 
-fn is_valid(rules: &RuleSet, manual: &Vec<u32>) -> bool {
-    for i in 0..manual.len() {
-        for j in 0..i {
-            if !rules.is_valid(manual[j], manual[i]) {
-                return false;
-            }
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(error) => write!(f, "{error}"),
+            Error::Order { update, error } => write!(f, "update {update}: {error}"),
         }
     }
-    return true;
 }
-pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, pages) = parse(input);
-    let valid = pages
-        .into_iter()
-        .filter(|v| is_valid(&rules, v))
-        .collect::<Vec<_>>();
 
-    let ans = valid.into_iter().map(|v| v[v.len() / 2]).sum();
+fn bit(page: u8) -> u128 {
+    1 << page
+}
+
+impl RuleSet {
+    fn insert(&mut self, from: u8, to: u8) {
+        self.before[from as usize] |= bit(to);
+        self.after[to as usize] |= bit(from);
+    }
+
+    fn is_before(&self, from: u8, to: u8) -> bool {
+        self.before[from as usize] & bit(to) != 0
+    }
+
+    /// Checks that the rules give a total order over the pages of `update`.
+    ///
+    /// Every pair of pages needs exactly one rule. The rules over `n` pages then form a tournament,
+    /// which is transitive exactly when every page has a different number of pages after it.
+    fn check(&self, update: &[u8]) -> Result<(), OrderError> {
+        let mask = update.iter().fold(0, |mask, &page| mask | bit(page));
+        let pages = || (0..PAGES as u8).filter(move |&page| mask & bit(page) != 0);
+
+        let mut by_rank = [None; PAGES];
+        for a in pages() {
+            let (before, after) = (
+                self.before[a as usize] & mask,
+                self.after[a as usize] & mask,
+            );
+            if let Some(b) = pages().find(|&b| before & after & bit(b) != 0) {
+                return Err(OrderError::Cycle(Cycle(vec![a, b])));
+            }
+            if let Some(b) = pages().find(|&b| b != a && (before | after) & bit(b) == 0) {
+                return Err(OrderError::Unrelated(a.min(b), a.max(b)));
+            }
+
+            let rank = before.count_ones() as usize;
+            match by_rank[rank] {
+                None => by_rank[rank] = Some(a),
+                Some(b) => {
+                    // `x` beats `y` and both beat as many pages, so something `y` beats beats `x`.
+                    let (x, y) = if self.is_before(a, b) { (a, b) } else { (b, a) };
+                    let z = pages()
+                        .find(|&z| self.is_before(y, z) && self.is_before(z, x))
+                        .expect("equal ranks in a tournament imply a 3-cycle");
+                    return Err(OrderError::Cycle(Cycle(vec![x, y, z])));
+                }
+            }
+        }
+
+        Ok(())
+    }
 
-    Some(ans)
+    fn is_valid(&self, update: &[u8]) -> bool {
+        update.is_sorted_by(|&a, &b| !self.is_before(b, a))
+    }
+
+    fn fix(&self, update: &mut [u8]) {
+        update.sort_by(|&a, &b| {
+            if self.is_before(a, b) {
+                Ordering::Less
+            } else if self.is_before(b, a) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        });
+    }
+}
+
+fn page(page: u8, text: &str) -> parse::Result<u8> {
+    if (page as usize) < PAGES {
+        Ok(page)
+    } else {
+        Err(ParseError::new(
+            format!("page {page} is not a two-digit number"),
+            text,
+        ))
+    }
 }
 
-fn fix(rules: &RuleSet, v: Vec<u32>) -> Vec<u32> {
-    let mut v = v;
-    let mut ans = Vec::new();
+fn parse(input: &str) -> parse::Result<(RuleSet, Vec<Vec<u8>>)> {
+    let sections = parse::sections(input);
+    let [rules, pages] = sections[..] else {
+        return Err(ParseError::new(
+            "expected rules and pages separated by a blank line",
+            input.trim_end(),
+        ));
+    };
+
+    let mut ruleset = RuleSet::default();
+    for (from, to) in rules.parse_lines(|line| {
+        let (from, to) = parse::split_once(line, "|")?;
+        Ok((page(parse::int(from)?, line)?, page(parse::int(to)?, line)?))
+    })? {
+        ruleset.insert(from, to);
+    }
 
-    let is_ok =
-        |v: &Vec<u32>, candidate: u32| v.iter().all(|other| rules.is_valid(candidate, *other));
+    let pages = pages.parse_lines(|line| {
+        parse::int_fields(line, line.split(','))?
+            .into_iter()
+            .map(|p| page(p, line))
+            .collect()
+    })?;
 
-    while !v.is_empty() {
-        let (pos, found) = v
-            .iter()
-            .enumerate()
-            .filter(|(p, candidate)| is_ok(&v, **candidate))
-            .next()
-            .unwrap();
+    Ok((ruleset, pages))
+}
 
-        ans.push(*found);
-        v.swap_remove(pos);
+/// Parses the input and checks that the rules order every update.
+fn updates(input: &str) -> Result<(RuleSet, Vec<Vec<u8>>), Error> {
+    let (rules, updates) = parse(input)?;
+    for (i, update) in updates.iter().enumerate() {
+        rules.check(update).map_err(|error| Error::Order {
+            update: i + 1,
+            error,
+        })?;
     }
+    Ok((rules, updates))
+}
 
-    ans
+fn middle(update: &[u8]) -> u32 {
+    update[update.len() / 2] as u32
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, pages) = parse(input);
-    let invalid = pages
-        .into_iter()
-        .filter(|v| !is_valid(&rules, v))
-        .collect::<Vec<_>>();
+pub fn part_one(input: &str) -> Result<u32, Error> {
+    let (rules, updates) = updates(input)?;
+    let ans = updates
+        .iter()
+        .filter(|v| rules.is_valid(v))
+        .map(|v| middle(v))
+        .sum();
 
-    let fixed = invalid
-        .into_iter()
-        .map(|v| fix(&rules, v))
-        .collect::<Vec<_>>();
+    Ok(ans)
+}
 
-    let ans = fixed.into_iter().map(|v| v[v.len() / 2]).sum();
+pub fn part_two(input: &str) -> Result<u32, Error> {
+    let (rules, updates) = updates(input)?;
+    let ans = updates
+        .into_iter()
+        .filter(|v| !rules.is_valid(v))
+        .map(|mut v| {
+            rules.fix(&mut v);
+            middle(&v)
+        })
+        .sum();
 
-    Some(ans)
+    Ok(ans)
 }
 
 #[cfg(test)]
@@ -106,19 +228,63 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let (rules, pages) = parse("1|2\n3|4\n\n1,2,3\n3,4");
+        let (_, pages) = parse("1|2\n3|4\n\n1,2,3\n3,4").unwrap();
         assert_eq!(pages, vec![vec![1, 2, 3], vec![3, 4]]);
     }
 
+    #[test]
+    fn test_parse_error() {
+        let err = parse("1|2\n3-4\n\n1,2,3").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.text, "3-4");
+
+        let err = parse("1|2\n\n1,2,x3\n4,5").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 5, "x3"));
+
+        let err = parse("1|2\n\n1,2,3\n4,99999999999").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+
+        let err = parse("1|2\n\n1,200").unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (3, "page 200 is not a two-digit number")
+        );
+    }
+
+    #[test]
+    fn test_order_errors() {
+        let unrelated = part_one("1|2\n3|4\n\n1,2\n1,3");
+        assert_eq!(
+            unrelated,
+            Err(Error::Order {
+                update: 2,
+                error: OrderError::Unrelated(1, 3)
+            })
+        );
+
+        let contradiction = part_two("1|2\n2|1\n\n1,2");
+        assert_eq!(
+            contradiction.unwrap_err().to_string(),
+            "update 1: graph contains a cycle: 1 -> 2 -> 1"
+        );
+
+        let (rules, _) = parse("1|2\n2|3\n3|1\n\n1").unwrap();
+        assert_eq!(
+            rules.check(&[3, 1, 2]),
+            Err(OrderError::Cycle(Cycle(vec![1, 2, 3])))
+        );
+        assert_eq!(rules.check(&[2, 1]), Ok(()));
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        assert_eq!(result, Ok(123));
     }
 }
@@ -1,11 +1,156 @@
+use advent_of_code::grid::Grid;
+use advent_of_code::point::Dir4;
+
 advent_of_code::solution!(6);
 
+const EXIT: usize = usize::MAX;
+
+struct Lab {
+    grid: Grid<char>,
+    start: usize,
+    // for every direction and cell: where the guard stops before the next obstacle, or `EXIT`.
+    jumps: [Vec<usize>; 4],
+}
+
+impl Lab {
+    fn parse(input: &str) -> Self {
+        let grid = Grid::parse(input);
+        let (row, col) = grid.find(&'^').expect("no guard in the lab");
+        let start = row * grid.cols() + col;
+
+        let jumps = Dir4::ALL.map(|dir| Self::build_jumps(&grid, dir));
+
+        Lab { grid, start, jumps }
+    }
+
+    fn build_jumps(grid: &Grid<char>, dir: Dir4) -> Vec<usize> {
+        let cols = grid.cols();
+        let mut jumps = vec![EXIT; grid.rows() * cols];
+
+        // the cell ahead has to be resolved first, so walk against the direction of movement.
+        let order: Box<dyn Iterator<Item = usize>> = match dir {
+            Dir4::Up | Dir4::Left => Box::new(0..jumps.len()),
+            Dir4::Down | Dir4::Right => Box::new((0..jumps.len()).rev()),
+        };
+        for cell in order {
+            jumps[cell] = match step(grid, cell, dir) {
+                None => EXIT,
+                Some(next) if grid[(next / cols, next % cols)] == '#' => cell,
+                Some(next) => jumps[next],
+            };
+        }
+
+        jumps
+    }
+
+    fn step(&self, cell: usize, dir: Dir4) -> Option<usize> {
+        step(&self.grid, cell, dir)
+    }
+
+    fn is_blocked(&self, cell: usize) -> bool {
+        let cols = self.grid.cols();
+        self.grid[(cell / cols, cell % cols)] == '#'
+    }
+
+    /// The original patrol: for every visited cell, the state right before it was first entered.
+    fn patrol(&self) -> Vec<Option<(usize, Dir4)>> {
+        let mut entered = vec![None; self.jumps[0].len()];
+        let (mut cell, mut dir) = (self.start, Dir4::Up);
+        entered[cell] = Some((cell, dir));
+
+        while let Some(next) = self.step(cell, dir) {
+            if self.is_blocked(next) {
+                dir = dir.turn_right();
+                continue;
+            }
+            if entered[next].is_none() {
+                entered[next] = Some((cell, dir));
+            }
+            cell = next;
+        }
+
+        entered
+    }
+
+    /// Whether the guard loops from `cell` facing `dir` with an extra obstacle at `obstacle`.
+    ///
+    /// The guard jumps from turn to turn; `seen` holds the `stamp` of every turn already taken.
+    fn loops(
+        &self,
+        mut cell: usize,
+        mut dir: Dir4,
+        obstacle: usize,
+        seen: &mut [u32],
+        stamp: u32,
+    ) -> bool {
+        let cols = self.grid.cols();
+        let (orow, ocol) = (obstacle / cols, obstacle % cols);
+
+        loop {
+            let state = cell * 4 + dir as usize;
+            if seen[state] == stamp {
+                return true;
+            }
+            seen[state] = stamp;
+
+            let (row, col) = (cell / cols, cell % cols);
+            let target = self.jumps[dir as usize][cell];
+            // the extra obstacle only matters if it is between the guard and where it would stop.
+            let hits_obstacle = match dir {
+                Dir4::Up => col == ocol && orow < row && (target == EXIT || orow >= target / cols),
+                Dir4::Down => {
+                    col == ocol && orow > row && (target == EXIT || orow <= target / cols)
+                }
+                Dir4::Left => {
+                    row == orow && ocol < col && (target == EXIT || ocol >= target % cols)
+                }
+                Dir4::Right => {
+                    row == orow && ocol > col && (target == EXIT || ocol <= target % cols)
+                }
+            };
+
+            cell = if hits_obstacle {
+                self.step(obstacle, dir.opposite())
+                    .expect("the guard reaches the obstacle from inside the lab")
+            } else if target == EXIT {
+                return false;
+            } else {
+                target
+            };
+            dir = dir.turn_right();
+        }
+    }
+}
+
+fn step(grid: &Grid<char>, cell: usize, dir: Dir4) -> Option<usize> {
+    let cols = grid.cols();
+    let (row, col) = grid.step((cell / cols, cell % cols), dir)?;
+    Some(row * cols + col)
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
-    None
+    let lab = Lab::parse(input);
+    Some(lab.patrol().iter().filter(|e| e.is_some()).count() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    None
+    let lab = Lab::parse(input);
+    let mut seen = vec![0; lab.jumps[0].len() * 4];
+
+    // an obstacle off the original path is never reached, so only those cells are worth trying.
+    let count = lab
+        .patrol()
+        .into_iter()
+        .enumerate()
+        .filter(|&(cell, _)| cell != lab.start)
+        .filter_map(|(cell, entered)| Some((cell, entered?)))
+        .zip(1..)
+        .filter(|&((obstacle, (cell, dir)), stamp)| {
+            lab.loops(cell, dir, obstacle, &mut seen, stamp)
+        })
+        .count();
+
+    Some(count as u32)
 }
 
 #[cfg(test)]
@@ -15,12 +160,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(6));
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use advent_of_code::operators::{Solver, ADD, CONCAT, MUL};
 
 advent_of_code::solution!(7);
 
@@ -24,121 +24,13 @@ impl From<&str> for Puzzle {
     }
 }
 
-fn log10ceil(x: u64) -> u64 {
-    let mut ans = 1;
-    while ans <= x {
-        ans *= 10;
-    }
-
-    ans
-}
-
-trait ReverseOp {
-    fn reverse(&self, target: u64) -> Vec<u64>;
-    fn value(&self) -> u64;
-}
-
-struct OpPart1(u64);
-
-impl ReverseOp for OpPart1 {
-    fn reverse(&self, target: u64) -> Vec<u64> {
-        let mut ans = Vec::new();
-
-        if target%self.0 == 0 {
-            ans.push(target/self.0);
-        }
-
-        if target>self.0 {
-            ans.push(target-self.0);
-        }
-
-        ans
-    }
-
-    fn value(&self) -> u64 {
-        self.0
-    }
-
-}
-
-struct OpPart2 {
-    operand: u64,
-    pow10: u64
-}
-
-impl OpPart2 {
-    fn new(v: u64) -> Self {
-        OpPart2 {
-            operand: v,
-            pow10: log10ceil(v)
-        }
-    }
-}
-
-impl ReverseOp for OpPart2 {
-    fn reverse(&self, target: u64) -> Vec<u64> {
-        let mut ans = Vec::new();
-
-        if target%self.operand == 0 {
-            ans.push(target/self.operand);
-        }
-
-        if target>self.operand {
-            ans.push(target-self.operand);
-        }
-
-        if target%self.pow10 == self.operand {
-            ans.push(target/self.pow10);
-        }
-
-        ans
-    }
-
-    fn value(&self) -> u64 {
-        self.operand
-    }
-}
-
-
-fn is_achieavable_fast(target: u64, operands: &[impl ReverseOp]) -> bool {
-    if operands.is_empty() {
-        panic!("SHould not happen");
-    }
-
-    if target <= 0 {
-        return false;
-    }
-    if operands.len() == 1 {
-        return  target == operands[0].value();
-    }
-
-
-    let rest = &operands[0..operands.len()-1];
-    let last = operands.last().unwrap();
-
-    let next_targets = last.reverse(target);
-
-    next_targets.iter()
-        .any(|p| is_achieavable_fast(*p, &rest))
-}
-
 impl Puzzle {
     fn is_achieavable_part_one(&self) -> bool {
-        let operands =
-            self.operands
-                .iter()
-                .map(|v| OpPart1(*v))
-                .collect::<Vec<_>>();
-        is_achieavable_fast(self.target, &operands)
+        Solver::new(&[ADD, MUL]).is_reachable(self.target, &self.operands)
     }
 
     fn is_achieavable_part_two(&self) -> bool {
-        let operands =
-            self.operands
-                .iter()
-                .map(|v| OpPart2::new(*v))
-                .collect::<Vec<_>>();
-        is_achieavable_fast(self.target, &operands)
+        Solver::new(&[ADD, MUL, CONCAT]).is_reachable(self.target, &self.operands)
     }
 }
 
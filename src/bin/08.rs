@@ -1,5 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::counter::group_pairs;
+use advent_of_code::grid::Grid;
+use advent_of_code::num::Integer;
+use advent_of_code::point::Point;
 use itertools::Itertools;
 
 advent_of_code::solution!(8);
@@ -7,72 +11,60 @@ advent_of_code::solution!(8);
 
 #[derive(Debug, Eq, PartialEq)]
 struct Field {
-    rows: usize,
-    cols: usize,
-    beacons: HashMap<char, Vec<(i64, i64)>>
+    grid: Grid<char>,
+    beacons: HashMap<char, Vec<Point>>
 }
 
 
 fn parse(input: &str) -> Field {
-    let lines = input.lines()
-        .map(|l| l.chars().collect())
-        .collect::<Vec<Vec<_>>>();
+    let grid = Grid::parse(input);
 
-    let rows = lines.len();
-    let cols = lines[0].len();
+    let beacons = group_pairs(
+        grid.iter()
+            .filter(|(_, &c)| c != '.')
+            .map(|(pos, &c)| (c, Point::from(pos))),
+    );
 
-
-    let mut beacons: HashMap<char, Vec<(i64, i64)>> = HashMap::new();
-
-    for i in 0..rows {
-        for j in 0..cols {
-            if lines[i][j] != '.' {
-                beacons.entry( lines[i][j]).or_default().push((i as i64,j as i64));
-            }
-        }
-    }
-
-    Field {
-        rows, cols, beacons
-    }
+    Field { grid, beacons }
 }
 
 
 impl Field {
-    fn gen_antinodes(&self)  -> Vec<(i64, i64)> {
-        let mut ans: HashSet<(i64, i64)> = HashSet::new();
-        for (_, positions) in &self.beacons {
+    fn gen_antinodes(&self)  -> Vec<Point> {
+        let mut ans: HashSet<Point> = HashSet::new();
+        for positions in self.beacons.values() {
             for (p1, p2) in positions.iter()
                 .cartesian_product(positions.iter())
                 .filter(|(p1, p2)| p1 != p2) {
-                    let dx = p2.0 - p1.0;
-                    let dy = p2.1 - p1.1;
-                    ans.insert((p1.0 - dx, p1.1 - dy));
+                    ans.insert(*p1 - (*p2 - *p1));
                 }
         }
 
         ans.into_iter().collect()
     }
 
-    fn contains(&self, (x, y): (i64, i64)) -> bool {
-        let range_rows = 0..(self.rows as i64);
-        let range_cols = 0..(self.cols as i64);
-
-        range_rows.contains(&x) && range_cols.contains(&y)
+    fn contains(&self, pos: Point) -> bool {
+        self.grid.contains(pos)
     }
 
-    fn gen_antinodes_part_two(&self)  -> Vec<(i64, i64)> {
-        let mut ans: HashSet<(i64, i64)> = HashSet::new();
-        for (_, positions) in &self.beacons {
-            for (p1, p2) in positions.iter()
-                .cartesian_product(positions.iter())
-                .filter(|(p1, p2)| p1 != p2) {
-                    let dx = p2.0 - p1.0;
-                    let dy = p2.1 - p1.1;
-                    for i in -70..70 {
-                        ans.insert((p1.0 - i*dx, p1.1 - i*dy));
+    /// Every grid point in line with two antennas of the same frequency.
+    ///
+    /// The step between two antennas is reduced by the gcd of its coordinates, so points in between
+    /// are included, and the line is walked in both directions until it leaves the grid.
+    fn gen_antinodes_part_two(&self)  -> Vec<Point> {
+        let mut ans: HashSet<Point> = HashSet::new();
+        for positions in self.beacons.values() {
+            for (p1, p2) in positions.iter().tuple_combinations() {
+                let delta = *p2 - *p1;
+                let step = delta / delta.row.gcd(delta.col);
+                for step in [step, -step] {
+                    let mut pos = *p1;
+                    while self.contains(pos) {
+                        ans.insert(pos);
+                        pos += step;
                     }
                 }
+            }
         }
 
         ans.into_iter().collect()
@@ -95,18 +87,13 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     let field = parse(input);
 
-    let antinodes = field.gen_antinodes_part_two();
-
-    let ans =
-        antinodes.iter()
-            .filter(|p| field.contains(**p))
-            .count();
-
-    Some(ans as u32)
+    Some(field.gen_antinodes_part_two().len() as u32)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::testing::XorShift;
+
     use super::*;
 
     #[test]
@@ -120,4 +107,45 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(34));
     }
+
+    // a cell is an antinode if it is collinear with two antennas of the same frequency.
+    fn part_two_brute_force(field: &Field) -> usize {
+        field.grid.positions()
+            .map(Point::from)
+            .filter(|&p| field.beacons.values().any(|positions| {
+                positions.iter().tuple_combinations().any(|(&a, &b)| {
+                    let (u, v) = (b - a, p - a);
+                    u.row * v.col == u.col * v.row
+                })
+            }))
+            .count()
+    }
+
+    #[test]
+    fn test_part_two_large_grid() {
+        let size = 400;
+        let mut rng = XorShift::new(8);
+
+        let mut rows = vec![vec!['.'; size]; size];
+        // a pair close together, whose resonant line spans the whole grid.
+        rows[200][200] = 'A';
+        rows[201][201] = 'A';
+        // and one whose step has a common factor, so there are antinodes between the antennas.
+        rows[10][10] = 'B';
+        rows[16][19] = 'B';
+        for frequency in ['a', 'b', 'c'] {
+            for _ in 0..4 {
+                let (row, col) = (rng.below(size as u64) as usize, rng.below(size as u64) as usize);
+                rows[row][col] = frequency;
+            }
+        }
+        let input: String = rows.iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect();
+
+        let field = parse(&input);
+        let expected = part_two_brute_force(&field);
+        assert!(expected >= size);
+        assert_eq!(part_two(&input), Some(expected as u32));
+    }
 }
@@ -1,6 +1,7 @@
-use std::collections::VecDeque;
-
-use rayon::iter::Empty;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
 
 advent_of_code::solution!(9);
 
@@ -14,13 +15,6 @@ impl Block {
     fn is_file(&self) -> bool {
         matches!(self, Block::File(_))
     }
-
-    fn file_id(&self) -> Option<u64> {
-        match &self {
-            Block::File(id) => Some(*id),
-            Block::Empty => None,
-        }
-    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -37,7 +31,7 @@ fn parse(input: &str) -> Vec<Segment> {
     let mut id = 0;
 
     for b in bytes {
-        let Some(val) = b.checked_sub('0' as u8) else {
+        let Some(val) = b.checked_sub(b'0') else {
             break;
         };
 
@@ -59,18 +53,6 @@ fn parse(input: &str) -> Vec<Segment> {
     res
 }
 
-fn dump<'a>(segments: impl Iterator<Item = &'a Segment>) {
-    for segment in segments {
-        let c = match segment.block {
-            Block::File(id) => format!("{}", id),
-            Block::Empty => ".".to_string(),
-        };
-        for _ in 0..segment.size {
-            print!("{}", c);
-        }
-    }
-}
-
 fn calc_answer(segmented: &[Segment]) -> u64 {
     let mut values: Vec<u64> = Vec::new();
     for segment in segmented {
@@ -115,7 +97,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 
             if last.size >= first.size {
                 segmented.push(Segment {
-                    block: last.block.clone(),
+                    block: last.block,
                     size: first.size,
                 });
                 original.push_back(Segment {
@@ -124,7 +106,7 @@ pub fn part_one(input: &str) -> Option<u64> {
                 });
             } else {
                 segmented.push(Segment {
-                    block: last.block.clone(),
+                    block: last.block,
                     size: last.size,
                 });
                 original.push_front(Segment {
@@ -145,93 +127,107 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     if let Some(last) = original.front() {
         if last.block.is_file() {
-            segmented.push(last.clone());
+            segmented.push(*last);
         }
     }
 
     Some(calc_answer(&segmented))
 }
 
-fn normalize_part_two(original: Vec<Segment>) -> Vec<Segment> {
-    let mut ans: Vec<Segment> = Vec::new();
-
-    for segment in original {
-        if ans.is_empty() || segment.block.is_file() || ans.last().unwrap().block.is_file() {
-            ans.push(segment);
-        } else {
-            let last = ans.last_mut().unwrap();
-            last.size += segment.size;
-        }
-    }
-    ans
+/// Free spans bucketed by length, each bucket a min-heap of `(start, len)`.
+///
+/// Files are at most 9 blocks long, so all spans of 9 or more blocks share the last bucket.
+#[derive(Default)]
+struct FreeSpans {
+    buckets: [BinaryHeap<Reverse<(i64, i64)>>; 10],
 }
 
-
-fn run_for_id(input: Vec<Segment>, id: u64) -> Vec<Segment> {
-    let mut original: VecDeque<_> = normalize_part_two(input).into();
-
-    let mut segmented: Vec<Segment> = Vec::new();
-
-    let to_add = {
-        let mut to_add: Vec<Segment> = Vec::new();
-
-        while let Some(back) = original.pop_back() {
-            if back.block.file_id() == Some(id) {
-                original.push_back(back);
-                break;
-            } else {
-                to_add.push(back);
-            }
+impl FreeSpans {
+    fn insert(&mut self, start: i64, len: i64) {
+        if len > 0 {
+            self.buckets[len.min(9) as usize].push(Reverse((start, len)));
         }
+    }
 
-        to_add.reverse();
-        to_add
-    };
-
-    let mut target = original.pop_back().unwrap();
-
-
-    while !original.is_empty() {
-        let first = original.pop_front().unwrap();
+    /// Takes `size` blocks from the leftmost span that fits and starts before `before`.
+    fn take(&mut self, size: i64, before: i64) -> Option<i64> {
+        let bucket = (size as usize..self.buckets.len())
+            .filter_map(|i| Some((self.buckets[i].peek()?.0 .0, i)))
+            .min()
+            .filter(|&(start, _)| start < before)?
+            .1;
+
+        let Reverse((start, len)) = self.buckets[bucket].pop().unwrap();
+        self.insert(start + size, len - size);
+        Some(start)
+    }
+}
 
-        if first.block.is_file() || first.size < target.size {
-            segmented.push(first);
-        } else {
-            segmented.push(target.clone());
-            target.block = Block::Empty;
-            if first.size != target.size {
-                segmented.push(Segment{
-                    block: Block::Empty,
-                    size: first.size - target.size,
-                });
+pub fn part_two(input: &str) -> Option<u64> {
+    let mut free = FreeSpans::default();
+    let mut files = Vec::new();
+    // a file of size 0 doesn't separate the spans around it.
+    let mut gap = (0, 0);
+    let mut pos = 0;
+    for segment in parse(input) {
+        match segment.block {
+            Block::File(id) => {
+                free.insert(gap.0, gap.1);
+                files.push((id, pos, segment.size));
+                gap = (pos + segment.size, 0);
             }
-            break;
+            Block::Empty => gap.1 += segment.size,
         }
+        pos += segment.size;
     }
-    segmented.extend(original.iter());
-    segmented.push(target);
-    segmented.extend_from_slice(&to_add);
-    segmented
-}
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut segments = parse(input);
-    let max_id =
-        segments.iter()
-        .map(|s| s.block.file_id().unwrap_or(0))
-        .max().unwrap();
-
-    for id in (0..max_id+1).rev() {
-        segments = run_for_id(segments, id);
+    let mut checksum = 0;
+    for (id, start, size) in files.into_iter().rev() {
+        // space freed by a file is never reused: all files left to move are in front of it.
+        let start = free.take(size, start).unwrap_or(start);
+        checksum += id * (2 * start + size - 1) as u64 * size as u64 / 2;
     }
 
-    Some(calc_answer(&segments))
+    Some(checksum)
 }
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::{interval::IntervalSet, testing::XorShift};
+
     use super::*;
 
+    // the straightforward version: scan all free spans for the first that fits.
+    fn part_two_reference(input: &str) -> u64 {
+        let mut free = IntervalSet::new();
+        let mut files = Vec::new();
+        let mut pos = 0;
+        for segment in parse(input) {
+            let range = pos..pos + segment.size;
+            match segment.block {
+                Block::File(id) => files.push((id, range)),
+                Block::Empty => free.insert(range),
+            }
+            pos += segment.size;
+        }
+
+        let mut checksum = 0;
+        for (id, range) in files.into_iter().rev() {
+            let size = range.end - range.start;
+            let range = match free.first_fit(size) {
+                Some(gap) if gap.start < range.start => {
+                    let moved = gap.start..gap.start + size;
+                    free.remove(moved.clone());
+                    moved
+                }
+                _ => range,
+            };
+            checksum += id * (range.start + range.end - 1) as u64 * size as u64 / 2;
+        }
+
+        checksum
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -249,4 +245,20 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(2858));
     }
+
+    #[test]
+    fn test_part_two_matches_reference() {
+        let mut rng = XorShift::new(9);
+        for _ in 0..500 {
+            let len = rng.below(200) as usize;
+            let input: String = (0..len)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            assert_eq!(
+                part_two(&input),
+                Some(part_two_reference(&input)),
+                "{input}"
+            );
+        }
+    }
 }
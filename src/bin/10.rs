@@ -0,0 +1,113 @@
+use advent_of_code::grid::Grid;
+
+advent_of_code::solution!(10);
+
+const PEAK: u8 = 9;
+// cells that can't be part of a trail, e.g. `.` in the smaller examples.
+const IMPASSABLE: u8 = u8::MAX;
+
+fn parse(input: &str) -> Grid<u8> {
+    Grid::parse_with(input, |c| c.to_digit(10).map_or(IMPASSABLE, |d| d as u8))
+}
+
+/// Cells grouped by height, so every level can be processed once its upper neighbours are done.
+fn by_height(grid: &Grid<u8>) -> Vec<Vec<(usize, usize)>> {
+    let mut levels = vec![Vec::new(); PEAK as usize + 1];
+    for (pos, &height) in grid.iter() {
+        if height <= PEAK {
+            levels[height as usize].push(pos);
+        }
+    }
+    levels
+}
+
+/// Propagates values from the peaks down to the trailheads, returning the value of every trailhead.
+///
+/// Every cell combines the values of its neighbours that are one step higher.
+fn descend<T: Clone>(
+    grid: &Grid<u8>,
+    empty: T,
+    peak: impl Fn(usize) -> T,
+    combine: impl Fn(&mut T, &T),
+) -> Vec<T> {
+    let levels = by_height(grid);
+    let mut values = grid.map(|_| empty.clone());
+
+    for (i, &pos) in levels[PEAK as usize].iter().enumerate() {
+        values[pos] = peak(i);
+    }
+    for height in (0..PEAK).rev() {
+        for &pos in &levels[height as usize] {
+            let mut value = empty.clone();
+            for next in grid.neighbours4(pos).filter(|&n| grid[n] == height + 1) {
+                combine(&mut value, &values[next]);
+            }
+            values[pos] = value;
+        }
+    }
+
+    levels[0].iter().map(|&pos| values[pos].clone()).collect()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid = parse(input);
+    let peaks = grid.iter().filter(|(_, &h)| h == PEAK).count();
+    let words = peaks.div_ceil(64);
+
+    // a bitset of the peaks reachable from every cell.
+    let trailheads = descend(
+        &grid,
+        vec![0u64; words],
+        |i| {
+            let mut bits = vec![0u64; words];
+            bits[i / 64] |= 1 << (i % 64);
+            bits
+        },
+        |acc, bits| acc.iter_mut().zip(bits).for_each(|(a, b)| *a |= b),
+    );
+
+    Some(
+        trailheads
+            .iter()
+            .map(|bits| bits.iter().map(|w| w.count_ones()).sum::<u32>())
+            .sum(),
+    )
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let grid = parse(input);
+
+    // the number of distinct trails from every cell.
+    let trailheads = descend(&grid, 0u64, |_| 1, |acc, ways| *acc += ways);
+
+    Some(trailheads.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(36));
+    }
+
+    #[test]
+    fn test_part_one_impassable() {
+        let input = "...0...\n...1...\n...2...\n6543456\n7.....7\n8.....8\n9.....9\n";
+        assert_eq!(part_one(input), Some(2));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(81));
+    }
+
+    #[test]
+    fn test_part_two_impassable() {
+        let input = ".....0.\n..4321.\n..5..2.\n..6543.\n..7..4.\n..8765.\n..9....\n";
+        assert_eq!(part_two(input), Some(3));
+    }
+}
@@ -1,72 +1,88 @@
-use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+use advent_of_code::num::Integer;
+use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(11);
 
-fn split(v: u64) -> Option<(u64, u64)> {
-    let mut pow10 = 10;
-    let mut cnt = 1;
-    while pow10 <= v {
-        pow10 *= 10;
-        cnt += 1
-    }
+#[derive(Debug, PartialEq, Eq)]
+pub enum BlinkError {
+    /// A stone engraved with this number can't be multiplied by 2024 without overflowing.
+    StoneOverflow(u64),
+    /// The number of stones no longer fits into a `u128` after this many blinks.
+    CountOverflow(usize),
+}
 
-    if cnt % 2 == 1 {
-        None
-    } else {
-        let mut pow10 = 1;
-        for _ in 0..cnt / 2 {
-            pow10 *= 10;
+impl Display for BlinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlinkError::StoneOverflow(stone) => {
+                write!(f, "stone {stone} overflows when multiplied by 2024")
+            }
+            BlinkError::CountOverflow(blink) => {
+                write!(f, "number of stones overflows after {blink} blinks")
+            }
         }
-        Some((v / pow10, v % pow10))
     }
 }
 
-
-fn solve(v: u64, time: u8, dp: &mut HashMap<(u64, u8), u64>) -> u64 {
-    if time == 0 {
-        return 1;
+fn blink(stone: u64) -> Result<(u64, Option<u64>), BlinkError> {
+    if stone == 0 {
+        return Ok((1, None));
     }
 
-    if v == 0 {
-        return solve(1, time - 1, dp)
+    match stone.split_digits() {
+        Some((l, r)) => Ok((l, Some(r))),
+        None => stone
+            .checked_mul(2024)
+            .map(|v| (v, None))
+            .ok_or(BlinkError::StoneOverflow(stone)),
     }
+}
 
-    if dp.contains_key(&(v, time)) {
-        return *dp.get(&(v, time)).unwrap();
+/// Number of stones after `blinks` blinks.
+///
+/// Stones don't affect each other and the same numbers keep coming up, so only the count per
+/// number is tracked from one blink to the next.
+pub fn count_stones(stones: &[u64], blinks: usize) -> Result<u128, BlinkError> {
+    let mut counts: FxHashMap<u64, u128> = FxHashMap::default();
+    for &stone in stones {
+        *counts.entry(stone).or_default() += 1;
     }
 
-    let res = if let Some((l, r)) = split(v) {
-        solve(l, time - 1, dp) + solve(r, time - 1, dp)
-    } else {
-        solve(2024 * v, time - 1, dp)
-    };
-
-    dp.insert((v, time), res);
-
-    res
-}
+    for i in 1..=blinks {
+        let mut next: FxHashMap<u64, u128> = FxHashMap::default();
+        for (stone, count) in counts {
+            let (l, r) = blink(stone)?;
+            for stone in std::iter::once(l).chain(r) {
+                let entry = next.entry(stone).or_default();
+                *entry = entry
+                    .checked_add(count)
+                    .ok_or(BlinkError::CountOverflow(i))?;
+            }
+        }
+        counts = next;
+    }
 
-fn sol_many(nums: &[u64], time: u8) -> u64 {
-    let mut dp = HashMap::new();
-    nums.iter().map(|v| solve(*v, time, &mut dp)).sum()
+    counts
+        .values()
+        .try_fold(0u128, |acc, &count| acc.checked_add(count))
+        .ok_or(BlinkError::CountOverflow(blinks))
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let nums: Vec<u64> = input
+fn parse(input: &str) -> Vec<u64> {
+    input
         .split_ascii_whitespace()
         .map(|s| s.parse().unwrap())
-        .collect();
-
-    Some(sol_many(&nums, 25))
+        .collect()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let nums: Vec<u64> = input
-        .split_ascii_whitespace()
-        .map(|s| s.parse().unwrap())
-        .collect();
+pub fn part_one(input: &str) -> Result<u128, BlinkError> {
+    count_stones(&parse(input), 25)
+}
 
-    Some(sol_many(&nums, 75))
+pub fn part_two(input: &str) -> Result<u128, BlinkError> {
+    count_stones(&parse(input), 75)
 }
 
 #[cfg(test)]
@@ -75,18 +91,37 @@ mod tests {
 
     #[test]
     fn test_split() {
-        assert_eq!(split(123), None);
-        assert_eq!(split(123023), Some((123, 23)));
+        assert_eq!(123u64.split_digits(), None);
+        assert_eq!(123023u64.split_digits(), Some((123, 23)));
     }
 
     #[test]
     fn test_samples() {
-        assert_eq!(sol_many(&[125, 17], 6), 22);
+        assert_eq!(count_stones(&[125, 17], 6), Ok(22));
+        assert_eq!(count_stones(&[125, 17], 0), Ok(2));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(
+            count_stones(&[u64::MAX / 1000], 1),
+            Err(BlinkError::StoneOverflow(u64::MAX / 1000))
+        );
+        assert!(matches!(
+            count_stones(&[125, 17], 1000),
+            Err(BlinkError::CountOverflow(_))
+        ));
     }
 
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
+        assert_eq!(result, Ok(55312));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Ok(65601038650482));
     }
 }
@@ -0,0 +1,189 @@
+//! Counting and grouping values.
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, AddAssign, Sub},
+};
+
+use rustc_hash::FxHashMap;
+
+/// A multiset: how often every value occurs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: FxHashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self {
+            counts: FxHashMap::default(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How often `value` occurs, `0` if it never does.
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, value: T) {
+        self.add_n(value, 1);
+    }
+
+    pub fn add_n(&mut self, value: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(value).or_default() += n;
+        }
+    }
+
+    /// Removes up to `n` occurrences of `value`.
+    pub fn remove_n(&mut self, value: &T, n: usize) {
+        if let Some(count) = self.counts.get_mut(value) {
+            *count = count.saturating_sub(n);
+            if *count == 0 {
+                self.counts.remove(value);
+            }
+        }
+    }
+
+    /// Number of distinct values.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of all counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// All distinct values with their counts, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(v, &c)| (v, c))
+    }
+
+    /// The `n` most common values, most common first. Ties are in no particular order.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        let mut all: Vec<_> = self.iter().collect();
+        all.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
+        all.truncate(n);
+        all
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl<T: Eq + Hash> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        for (value, count) in other.counts {
+            self.add_n(value, count);
+        }
+    }
+}
+
+/// Sums the counts of both counters.
+impl<T: Eq + Hash> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+/// Subtracts the counts of `other`, dropping values that reach zero.
+impl<T: Eq + Hash> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        for (value, count) in other.counts {
+            self.remove_n(&value, count);
+        }
+        self
+    }
+}
+
+/// Groups `items` by the key `key` computes for each, keeping their order within a group.
+pub fn group_by_key<K, V>(
+    items: impl IntoIterator<Item = V>,
+    mut key: impl FnMut(&V) -> K,
+) -> HashMap<K, Vec<V>>
+where
+    K: Eq + Hash,
+{
+    group_pairs(items.into_iter().map(|item| (key(&item), item)))
+}
+
+/// Collects `(key, value)` pairs into a list of values per key.
+pub fn group_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> HashMap<K, Vec<V>>
+where
+    K: Eq + Hash,
+{
+    let mut groups: HashMap<K, Vec<V>> = HashMap::new();
+    for (key, value) in pairs {
+        groups.entry(key).or_default().push(value);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(counter.count(&'a'), 5);
+        assert_eq!(counter.count(&'z'), 0);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.total(), 11);
+        assert_eq!(counter.most_common(1), vec![(&'a', 5)]);
+        // 'b' and 'r' tie for second place.
+        assert_eq!(counter.most_common(2)[1].1, 2);
+        assert_eq!(counter.most_common(10).len(), 5);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a: Counter<u32> = [1, 1, 2, 3].into_iter().collect();
+        let b: Counter<u32> = [1, 2, 2, 4].into_iter().collect();
+
+        let sum = a.clone() + b.clone();
+        assert_eq!((sum.count(&1), sum.count(&2), sum.total()), (3, 3, 8));
+
+        let diff = a - b;
+        assert_eq!(diff, [1, 3].into_iter().collect());
+    }
+
+    #[test]
+    fn test_group() {
+        let groups = group_by_key(["apple", "avocado", "banana"], |s| s.as_bytes()[0]);
+        assert_eq!(groups[&b'a'], vec!["apple", "avocado"]);
+        assert_eq!(groups[&b'b'], vec!["banana"]);
+
+        let groups = group_pairs([('x', 1), ('y', 2), ('x', 3)]);
+        assert_eq!(groups[&'x'], vec![1, 3]);
+    }
+}
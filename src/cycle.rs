@@ -0,0 +1,211 @@
+//! Cycle detection for simulations that eventually repeat.
+//!
+//! A sequence `x0, x1 = f(x0), x2 = f(x1), ...` of a deterministic step function `f` over a finite
+//! state space always ends in a loop. [`Period`] describes where that loop starts and how long it is,
+//! which is enough to jump to step one billion without simulating it.
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence starts repeating: `x[start + len] == x[start]`, with both as small as possible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Period {
+    /// The earliest step with the same state as step `n`.
+    pub fn index_at(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// The state at step `n`, given the states of (at least) the first `start + len` steps.
+    pub fn state_at<'a, S>(&self, history: &'a [S], n: usize) -> &'a S {
+        &history[self.index_at(n)]
+    }
+}
+
+/// Brent's algorithm: needs `O(start + len)` steps and constant memory.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Period {
+    // find the cycle length by racing a hare against a tortoise that teleports at powers of two.
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = step(&hare);
+        len += 1;
+    }
+
+    // with the hare `len` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Period { start, len }
+}
+
+/// Floyd's tortoise and hare: like [`brent`], but usually needs more steps.
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Period {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        len += 1;
+    }
+
+    Period { start, len }
+}
+
+/// Remembers the step at which every state was first seen.
+///
+/// Useful when the simulation loop is driven by hand, or when only part of the state has to repeat.
+#[derive(Debug, Clone)]
+pub struct SeenStates<K> {
+    seen: HashMap<K, usize>,
+    steps: usize,
+}
+
+impl<K> Default for SeenStates<K> {
+    fn default() -> Self {
+        Self {
+            seen: HashMap::new(),
+            steps: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash> SeenStates<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the state of the next step, returning the cycle if it was seen before.
+    pub fn insert(&mut self, key: K) -> Option<Period> {
+        let step = self.steps;
+        self.steps += 1;
+        match self.seen.get(&key) {
+            Some(&start) => Some(Period {
+                start,
+                len: step - start,
+            }),
+            None => {
+                self.seen.insert(key, step);
+                None
+            }
+        }
+    }
+
+    /// Number of states recorded so far.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+}
+
+/// Simulates until a state repeats, returning the cycle and all states up to its first repetition.
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> (Period, Vec<S>) {
+    let mut seen = SeenStates::new();
+    let mut history = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(period) = seen.insert(state.clone()) {
+            return (period, history);
+        }
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` steps, skipping over full cycles.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (period, history) = find_cycle(initial, step);
+    period.state_at(&history, n).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 6, 3, 4, 5, 6, ...
+    fn step(x: &u32) -> u32 {
+        if *x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    const PERIOD: Period = Period { start: 3, len: 4 };
+
+    #[test]
+    fn test_brent_and_floyd() {
+        assert_eq!(brent(0, step), PERIOD);
+        assert_eq!(floyd(0, step), PERIOD);
+        assert_eq!(brent(4, step), Period { start: 0, len: 4 });
+        assert_eq!(floyd(7, |_| 7), Period { start: 0, len: 1 });
+    }
+
+    #[test]
+    fn test_seen_states() {
+        let mut seen = SeenStates::new();
+        let mut x = 0;
+        let period = loop {
+            if let Some(period) = seen.insert(x) {
+                break period;
+            }
+            x = step(&x);
+        };
+        assert_eq!(period, PERIOD);
+        assert_eq!(seen.steps(), 8);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let (period, history) = find_cycle(0, step);
+        assert_eq!(period, PERIOD);
+        assert_eq!(history, vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(period.index_at(2), 2);
+        assert_eq!(period.index_at(7), 3);
+        assert_eq!(
+            *period.state_at(&history, 1_000_000_000),
+            3 + (1_000_000_000 - 3) % 4
+        );
+        assert_eq!(state_at(0, step, 10), 6);
+    }
+}
@@ -0,0 +1,396 @@
+//! Generic graph searches.
+//!
+//! Graphs are never built up front: every search takes a `neighbours` closure, so the same code
+//! works on adjacency maps (`|n| rules[n].iter().copied()`) and on implicit graphs like grid cells
+//! (`|&p| grid.neighbours4(p).filter(...)`).
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::{self, Debug, Display},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Breadth-first search from all `starts`, returning the distance (in edges) to every reachable node.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut dist = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if dist.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, d)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if !dist.contains_key(&next) {
+                dist.insert(next.clone(), d + 1);
+                queue.push_back((next, d + 1));
+            }
+        }
+    }
+
+    dist
+}
+
+/// Depth-first search from `start`, returning every reachable node in the order it was visited.
+pub fn dfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<_> = neighbours(&node)
+            .into_iter()
+            .filter(|n| !seen.contains(n))
+            .collect();
+        // visit neighbours in the order they were returned.
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// Result of [`dijkstra_all`]: distances and every predecessor on a shortest path.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<N, C> {
+    pub dist: HashMap<N, C>,
+    pub preds: HashMap<N, Vec<N>>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> ShortestPaths<N, C> {
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    /// One shortest path from a start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.dist.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(prev) = self.preds.get(path.last().unwrap()).and_then(|p| p.first()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All nodes that lie on any shortest path to one of `targets`, including the targets.
+    pub fn nodes_on_paths_to(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<N> = targets
+            .into_iter()
+            .filter(|t| self.dist.contains_key(t))
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if seen.insert(node.clone()) {
+                stack.extend(self.preds.get(&node).into_iter().flatten().cloned());
+            }
+        }
+
+        seen
+    }
+}
+
+/// Dijkstra from all `starts`, exploring the whole reachable graph.
+///
+/// `C::default()` is used as the zero cost. Every predecessor on a shortest path is kept,
+/// so all shortest paths can be recovered from the result.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> ShortestPaths<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = ShortestPaths {
+        dist: HashMap::new(),
+        preds: HashMap::new(),
+    };
+    let mut nodes = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if paths.dist.insert(start.clone(), C::default()).is_none() {
+            heap.push(Reverse((C::default(), nodes.len())));
+            nodes.push(start);
+        }
+    }
+
+    while let Some(Reverse((cost, idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if paths.dist[&node] < cost {
+            continue;
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            match paths.dist.get(&next) {
+                Some(&best) if best < next_cost => {}
+                Some(&best) if best == next_cost => {
+                    paths.preds.entry(next).or_default().push(node.clone());
+                }
+                _ => {
+                    paths.dist.insert(next.clone(), next_cost);
+                    paths.preds.insert(next.clone(), vec![node.clone()]);
+                    heap.push(Reverse((next_cost, nodes.len())));
+                    nodes.push(next);
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// Cheapest path from `start` to the first node matching `is_goal`, with its cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search from `start` to the first node matching `is_goal`.
+///
+/// `heuristic` must never overestimate the remaining cost, otherwise the path may not be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // nodes are referred to by index, so `N` doesn't have to be `Ord`.
+    let mut nodes = vec![start.clone()];
+    let mut best: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut parent = vec![usize::MAX];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        let node = nodes[idx].clone();
+        if best[&node] < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            let mut path = vec![node];
+            let mut i = idx;
+            while parent[i] != usize::MAX {
+                i = parent[i];
+                path.push(nodes[i].clone());
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_some_and(|&c| c <= next_cost) {
+                continue;
+            }
+            let next_idx = nodes.len();
+            best.insert(next.clone(), next_cost);
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, next_idx)));
+            parent.push(idx);
+            nodes.push(next);
+        }
+    }
+
+    None
+}
+
+/// Returned by [`topological_sort`] when the graph has a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle: ")?;
+        for node in &self.0 {
+            write!(f, "{node:?} -> ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Orders `nodes` so that every edge points forward.
+///
+/// Only edges between the given nodes are considered, so this can sort a subset of a larger graph.
+/// Ties are broken by the order of `nodes`. If the edges contain a cycle, one such cycle is returned.
+pub fn topological_sort<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let nodes: Vec<N> = nodes.into_iter().collect();
+    let index: HashMap<&N, usize> = nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+
+    let mut edges = vec![Vec::new(); nodes.len()];
+    let mut incoming = vec![Vec::new(); nodes.len()];
+    for (from, node) in nodes.iter().enumerate() {
+        for next in neighbours(node) {
+            if let Some(&to) = index.get(&next) {
+                edges[from].push(to);
+                incoming[to].push(from);
+            }
+        }
+    }
+
+    let mut in_degree: Vec<usize> = incoming.iter().map(Vec::len).collect();
+    let mut queue: VecDeque<usize> = (0..nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+    let mut order = Vec::with_capacity(nodes.len());
+
+    while let Some(i) = queue.pop_front() {
+        order.push(i);
+        for &j in &edges[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                queue.push_back(j);
+            }
+        }
+    }
+
+    if order.len() < nodes.len() {
+        // every unsorted node still has an unsorted predecessor, so walking backwards must loop.
+        let mut seen = vec![usize::MAX; nodes.len()];
+        let mut walk = Vec::new();
+        let mut i = (0..nodes.len()).find(|&i| in_degree[i] > 0).unwrap();
+        while seen[i] == usize::MAX {
+            seen[i] = walk.len();
+            walk.push(i);
+            i = *incoming[i].iter().find(|&&j| in_degree[j] > 0).unwrap();
+        }
+        let mut cycle: Vec<N> = walk[seen[i]..].iter().map(|&i| nodes[i].clone()).collect();
+        cycle.reverse();
+        return Err(Cycle(cycle));
+    }
+
+    Ok(order.into_iter().map(|i| nodes[i].clone()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S..#
+.#..
+...E
+";
+
+    fn adjacency() -> HashMap<u32, Vec<u32>> {
+        HashMap::from([(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![])])
+    }
+
+    #[test]
+    fn test_bfs_on_grid() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        let dist = bfs([start], |&p| {
+            grid.neighbours4(p)
+                .filter(|&n| grid[n] != '#')
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(dist[&end], 5);
+        assert!(!dist.contains_key(&(0, 3)));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let adj = adjacency();
+        assert_eq!(dfs(1, |n| adj[n].clone()), vec![1, 2, 4, 3]);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+        let neighbours = |&p: &(usize, usize)| {
+            grid.neighbours4(p)
+                .filter(|&n| grid[n] != '#')
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let (cost, path) = dijkstra(start, neighbours, |&p| p == end).unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!((path[0], path[5]), (start, end));
+
+        let manhattan = |&(r, c): &(usize, usize)| end.0.abs_diff(r) + end.1.abs_diff(c);
+        let (cost, _) = astar(start, neighbours, manhattan, |&p| p == end).unwrap();
+        assert_eq!(cost, 5);
+
+        assert_eq!(dijkstra(start, neighbours, |&p| p == (0, 3)), None);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let adj = adjacency();
+        let paths = dijkstra_all([1], |n| adj[n].iter().map(|&m| (m, 1u32)));
+        assert_eq!(paths.distance(&4), Some(2));
+        assert_eq!(paths.path_to(&4).unwrap().len(), 3);
+        assert_eq!(paths.preds[&4], vec![2, 3]);
+        assert_eq!(paths.nodes_on_paths_to([4]), HashSet::from([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let adj = adjacency();
+        assert_eq!(
+            topological_sort([4, 3, 2, 1], |n| adj[n].clone()),
+            Ok(vec![1, 2, 3, 4])
+        );
+        // edges to nodes outside the subset are ignored.
+        assert_eq!(topological_sort([4, 2], |n| adj[n].clone()), Ok(vec![2, 4]));
+    }
+
+    #[test]
+    fn test_topological_sort_cycle() {
+        let adj = HashMap::from([(1, vec![2]), (2, vec![3]), (3, vec![1]), (0, vec![1])]);
+        let Err(Cycle(cycle)) = topological_sort([0, 1, 2, 3], |n| adj[n].clone()) else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(
+            Cycle(vec![1, 2, 3]).to_string(),
+            "graph contains a cycle: 1 -> 2 -> 3 -> 1"
+        );
+    }
+}
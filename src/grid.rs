@@ -0,0 +1,369 @@
+//! A dense 2D grid, as found in many puzzle inputs.
+//!
+//! Cells are indexed by `(row, col)` tuples or by [`Point`]s. Accessors that may leave the grid take
+//! a [`Point`] and return `None` outside of it, which makes walking off the edge cheap to check.
+//! Directions are [`Dir4`], [`Dir8`] or any other [`Vec2`] offset.
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::point::{Dir4, Dir8, Point, Vec2};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from row-major `data`.
+    ///
+    /// # Panics
+    /// If `data.len()` is not `rows * cols`.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        assert_eq!(data.len(), rows * cols, "grid data has the wrong size");
+        Self { rows, cols, data }
+    }
+
+    /// Parses one row per line, mapping every character with `f`.
+    ///
+    /// # Panics
+    /// If the lines have different lengths.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut data = Vec::with_capacity(input.len());
+        let mut rows = 0;
+        let mut cols = None;
+
+        for line in input.lines() {
+            let len = data.len();
+            data.extend(line.chars().map(&mut f));
+            let line_cols = data.len() - len;
+            match cols {
+                None => cols = Some(line_cols),
+                Some(c) => assert_eq!(c, line_cols, "line {} has a different length", rows + 1),
+            }
+            rows += 1;
+        }
+
+        Self {
+            rows,
+            cols: cols.unwrap_or(0),
+            data,
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Whether a position lies inside the grid.
+    pub fn contains(&self, pos: impl Into<Point>) -> bool {
+        self.to_unsigned(pos.into()).is_some()
+    }
+
+    /// The `(row, col)` of a point inside the grid.
+    pub fn to_unsigned(&self, pos: Point) -> Option<(usize, usize)> {
+        let (row, col) = pos.to_unsigned()?;
+        (row < self.rows && col < self.cols).then_some((row, col))
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        (row < self.rows && col < self.cols).then(|| &self.data[row * self.cols + col])
+    }
+
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        (row < self.rows && col < self.cols).then(|| &mut self.data[row * self.cols + col])
+    }
+
+    /// Iterates over all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| (i / cols, i % cols))
+    }
+
+    /// Iterates over all cells and their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.data.iter())
+    }
+
+    /// Position of the first cell (in row-major order) matching `pred`.
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter().find(|(_, v)| pred(v)).map(|(p, _)| p)
+    }
+
+    /// Positions of all cells matching `pred`.
+    pub fn positions_where<'a>(
+        &'a self,
+        pred: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter().filter(move |(_, v)| pred(v)).map(|(p, _)| p)
+    }
+
+    /// The neighbour of `pos` in direction `dir`, if it lies inside the grid.
+    pub fn step(&self, pos: impl Into<Point>, dir: impl Into<Vec2>) -> Option<(usize, usize)> {
+        self.to_unsigned(pos.into() + dir.into())
+    }
+
+    /// The up to 4 orthogonal neighbours of `pos` that lie inside the grid, in [`Dir4::ALL`] order.
+    pub fn neighbours4(&self, pos: impl Into<Point>) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let pos = pos.into();
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The up to 8 orthogonal and diagonal neighbours of `pos` that lie inside the grid, in
+    /// [`Dir8::ALL`] order.
+    pub fn neighbours8(&self, pos: impl Into<Point>) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let pos = pos.into();
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// Walks from `start` (inclusive) in steps of `dir` until leaving the grid.
+    pub fn ray(
+        &self,
+        start: impl Into<Point>,
+        dir: impl Into<Vec2>,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        let (start, dir) = (start.into(), dir.into());
+        (0..)
+            .map(move |i| self.to_unsigned(start + dir * i))
+            .take_while(Option::is_some)
+            .flatten()
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// # Panics
+    /// If `col` is out of bounds, like [`Grid::row`] and indexing.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            col < self.cols,
+            "column {col} out of bounds for a grid with {} columns",
+            self.cols
+        );
+        self.data.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn row_iter(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.cols.max(1))
+    }
+
+    pub fn column_iter(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// Builds a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses one row of characters per line.
+    pub fn parse(input: &str) -> Self {
+        Self::parse_with(input, |c| c)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![value; rows * cols],
+        }
+    }
+
+    fn build(rows: usize, cols: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        let data = (0..rows * cols).map(|i| f(i / cols, i % cols)).collect();
+        Self { rows, cols, data }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::build(self.cols, self.rows, |r, c| self[(c, r)].clone())
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self {
+        Self::build(self.cols, self.rows, |r, c| {
+            self[(self.rows - 1 - c, r)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self {
+        Self::build(self.cols, self.rows, |r, c| {
+            self[(c, self.cols - 1 - r)].clone()
+        })
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Position of the first cell (in row-major order) equal to `value`.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)> {
+        self.position(|v| v == value)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "position ({row}, {col}) is outside of the {}x{} grid",
+            self.rows,
+            self.cols
+        );
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "position ({row}, {col}) is outside of the {}x{} grid",
+            self.rows,
+            self.cols
+        );
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+/// Indexing by [`Point`] panics outside of the grid, like indexing by `(row, col)`.
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        match self.to_unsigned(pos) {
+            Some(pos) => &self[pos],
+            None => panic!("{pos:?} is outside of the {}x{} grid", self.rows, self.cols),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        match self.to_unsigned(pos) {
+            Some(pos) => &mut self[pos],
+            None => panic!("{pos:?} is outside of the {}x{} grid", self.rows, self.cols),
+        }
+    }
+}
+
+/// Prints the grid back in puzzle format, one row per line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.row_iter() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.to_string(), INPUT);
+    }
+
+    #[test]
+    fn test_point_access() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.to_unsigned(Point::new(-1, 0)), None);
+        assert_eq!(grid.to_unsigned(Point::new(0, 3)), None);
+        assert_eq!(grid[Point::new(1, 2)], 'f');
+        assert!(!grid.contains(Point::new(2, 0)));
+        assert!(grid.contains(Point::new(1, 0)));
+        assert_eq!(grid.step(Point::new(0, 0), Dir4::Right), Some((0, 1)));
+        assert_eq!(grid.step(Point::new(0, 0), Dir4::Up), None);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse(INPUT);
+        let n4: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(n4, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8(Point::new(0, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::parse(INPUT);
+        let ray: String = grid
+            .ray(Point::new(0, 0), Dir8::SE)
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "ae");
+        assert_eq!(grid.ray(Point::new(-1, 0), Dir4::Down).count(), 0);
+        let ray: String = grid
+            .ray(Point::new(0, 2), Vec2::new(0, -2))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "ca");
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(&'x'), None);
+        let vowels: Vec<_> = grid.positions_where(|c| "aeiou".contains(*c)).collect();
+        assert_eq!(vowels, vec![(0, 0), (1, 1)]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        let col: String = grid.column(2).collect();
+        assert_eq!(col, "cf");
+        assert_eq!(grid.column_iter().count(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of bounds")]
+    fn test_column_out_of_bounds() {
+        let grid = Grid::parse(INPUT);
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = Grid::parse(INPUT);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
@@ -0,0 +1,266 @@
+//! Sets of integers stored as disjoint half-open ranges.
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug},
+    ops::{Add, Range, Sub},
+};
+
+/// A set of values stored as sorted, disjoint, non-adjacent `start..end` ranges.
+///
+/// Overlapping or touching ranges are merged on insert, so `0..2` and `2..5` become `0..5`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    // start -> end of every range.
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterates over the ranges in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of disjoint ranges, not the number of values.
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    /// Adds all values of `range`, merging it with its neighbours.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(e);
+            }
+        }
+
+        let touching: Vec<T> = self.ranges.range(start..=end).map(|(&s, _)| s).collect();
+        for s in touching {
+            end = end.max(self.ranges.remove(&s).unwrap());
+        }
+
+        self.ranges.insert(start, end);
+    }
+
+    /// Removes all values of `range`, splitting ranges that stick out on either side.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.ranges.insert(s, range.start);
+                if e > range.end {
+                    self.ranges.insert(range.end, e);
+                }
+            }
+        }
+
+        let inside: Vec<(T, T)> = self
+            .ranges
+            .range(range.start..range.end)
+            .map(|(&s, &e)| (s, e))
+            .collect();
+        for (s, e) in inside {
+            self.ranges.remove(&s);
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+            }
+        }
+    }
+
+    /// All values in `self` or `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    /// All values in both `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = Self::new();
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let start = x.start.max(y.start);
+            let end = x.end.min(y.end);
+            if start < end {
+                result.ranges.insert(start, end);
+            }
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        result
+    }
+
+    /// Splits into the values below `at` and the values from `at` on.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = self.clone();
+        let mut above = self.clone();
+        if let Some(first) = self.ranges.keys().next() {
+            below.remove(at.max(*first)..self.end().unwrap().max(at));
+            above.remove(*first.min(&at)..at);
+        }
+        (below, above)
+    }
+
+    /// First range of the set that is at least `len` long.
+    pub fn first_fit(&self, len: T) -> Option<Range<T>> {
+        self.iter().find(|r| r.end - r.start >= len)
+    }
+
+    /// First gap between the ranges, inside `bounds`, that is at least `len` long.
+    pub fn first_gap(&self, len: T, bounds: Range<T>) -> Option<Range<T>> {
+        let mut start = bounds.start;
+        for range in self.iter().chain(std::iter::once(bounds.end..bounds.end)) {
+            let end = range.start.min(bounds.end);
+            if end > start && end - start >= len {
+                return Some(start..end);
+            }
+            start = start.max(range.end);
+            if start >= bounds.end {
+                break;
+            }
+        }
+        None
+    }
+
+    fn end(&self) -> Option<T> {
+        self.ranges.values().next_back().copied()
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T> From<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from(range: Range<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+
+impl<T: Debug> Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set()
+            .entries(self.ranges.iter().map(|(s, e)| s..e))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<Range<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let set: IntervalSet<i32> = [5..7, 0..2, 2..3, 10..12, 6..11].into_iter().collect();
+        assert_eq!(ranges(&set), vec![0..3, 5..12]);
+        assert!(set.contains(2));
+        assert!(!set.contains(3));
+        assert!(set.contains(11));
+        assert!(!set.contains(-1));
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut set = IntervalSet::from(0..10);
+        set.remove(3..5);
+        assert_eq!(ranges(&set), vec![0..3, 5..10]);
+        set.remove(2..6);
+        assert_eq!(ranges(&set), vec![0..2, 6..10]);
+        set.remove(-5..7);
+        assert_eq!(ranges(&set), vec![7..10]);
+        set.remove(0..20);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_union_intersection() {
+        let a: IntervalSet<i32> = [0..5, 10..15].into_iter().collect();
+        let b: IntervalSet<i32> = [3..12, 14..20].into_iter().collect();
+        assert_eq!(ranges(&a.union(&b)), vec![0..20]);
+        assert_eq!(ranges(&a.intersection(&b)), vec![3..5, 10..12, 14..15]);
+    }
+
+    #[test]
+    fn test_split_at() {
+        let set: IntervalSet<i32> = [0..5, 10..15].into_iter().collect();
+        let (below, above) = set.split_at(3);
+        assert_eq!(ranges(&below), vec![0..3]);
+        assert_eq!(ranges(&above), vec![3..5, 10..15]);
+
+        let (below, above) = set.split_at(20);
+        assert_eq!((below, above.is_empty()), (set.clone(), true));
+    }
+
+    #[test]
+    fn test_fit_and_gap() {
+        let set: IntervalSet<i32> = [0..2, 3..4, 6..9, 12..14].into_iter().collect();
+        assert_eq!(set.first_fit(3), Some(6..9));
+        assert_eq!(set.first_fit(4), None);
+
+        assert_eq!(set.first_gap(1, 0..20), Some(2..3));
+        assert_eq!(set.first_gap(3, 0..20), Some(9..12));
+        assert_eq!(set.first_gap(4, 0..20), Some(14..20));
+        assert_eq!(set.first_gap(4, 0..17), None);
+        assert_eq!(set.first_gap(2, 7..11), Some(9..11));
+    }
+}
@@ -1,3 +1,15 @@
+pub mod counter;
+pub mod cycle;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod memo;
+pub mod num;
+pub mod operators;
+pub mod parse;
+pub mod point;
+pub mod scanner;
 pub mod template;
+pub mod testing;
 
 // Use this file to add helper functions and additional modules.
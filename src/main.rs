@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, dashboard, download, inputs, read, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,7 +9,7 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{commands::inputs, Day};
     use std::process;
 
     pub enum AppArguments {
@@ -21,16 +23,28 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            overwrite_data: bool,
+            dry_run: bool,
+            template: Option<String>,
+            example_part_one: Option<String>,
+            example_part_two: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            watch: bool,
+            test: bool,
         },
         All {
             release: bool,
         },
+        Dashboard,
+        Inputs {
+            action: inputs::Action,
+            day: Option<Day>,
+        },
         Time {
             all: bool,
             day: Option<Day>,
@@ -47,6 +61,7 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
+            Some("dashboard") => AppArguments::Dashboard,
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
@@ -60,6 +75,10 @@ mod args {
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
+            Some("inputs") => AppArguments::Inputs {
+                action: args.free_from_str()?,
+                day: args.opt_free_from_str()?,
+            },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
@@ -67,12 +86,19 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                overwrite_data: args.contains("--overwrite-data"),
+                dry_run: args.contains("--dry-run"),
+                template: args.opt_value_from_str("--template")?,
+                example_part_one: args.opt_value_from_str("--example-1")?,
+                example_part_two: args.opt_value_from_str("--example-2")?,
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
+                test: args.contains("--test"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -103,31 +129,50 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
+            AppArguments::Dashboard => dashboard::handle(),
             AppArguments::Time { day, all, store } => time::handle(day, all, store),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Inputs { action, day } => inputs::handle(action, day),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
-            }
+                overwrite_data,
+                dry_run,
+                template,
+                example_part_one,
+                example_part_two,
+            } => scaffold::handle(
+                day,
+                &scaffold::Options {
+                    overwrite,
+                    overwrite_data,
+                    download,
+                    dry_run,
+                    template: template.as_deref(),
+                    examples: [example_part_one, example_part_two],
+                },
+            ),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                test,
+            } => solve::handle(day, release, dhat, submit, watch, test),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        scaffold::handle(
+                            day,
+                            &scaffold::Options {
+                                download: true,
+                                ..Default::default()
+                            },
+                        );
                         read::handle(day)
                     }
                     None => {
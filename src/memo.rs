@@ -0,0 +1,132 @@
+//! Memoization for recursive solutions.
+//!
+//! ```ignore
+//! fn count(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//!     memo.get_or_compute(n, |memo| if n < 2 { n } else { count(n - 1, memo) + count(n - 2, memo) })
+//! }
+//! ```
+use std::hash::Hash;
+
+use rustc_hash::FxHashMap;
+
+/// Cache hits and misses of a [`Memo`], for checking whether a cache key is worth it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Stats {
+    /// Share of lookups that were answered from the cache, between 0 and 1.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// A cache of computed values, keyed with the fast (but not DoS-resistant) Fx hasher.
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: FxHashMap<K, V>,
+    stats: Stats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: FxHashMap::default(),
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `key`, or computes it with `f` and caches it.
+    ///
+    /// `f` receives the memo itself, so it can recurse.
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = f(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Drops all cached values and resets the statistics.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.stats = Stats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1, memo) + fib(n - 2, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn test_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.get(&10), Some(&55));
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut memo = Memo::new();
+        fib(10, &mut memo);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 8,
+                misses: 11
+            }
+        );
+
+        fib(10, &mut memo);
+        assert_eq!(memo.stats().hits, 9);
+        assert!(memo.stats().hit_rate() > 0.4);
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.stats(), Stats::default());
+    }
+}
@@ -0,0 +1,413 @@
+//! Digit manipulation and number theory for the primitive integer types.
+//!
+//! All operations are methods of [`Integer`], so they read like the std ones:
+//! `a.gcd(b)`, `x.checked_concat(y)`, `b.mod_pow(e, m)`. Anything that can overflow has a `checked_`
+//! variant returning `None`; the plain variant panics instead.
+//! Modular arithmetic is done in `u128` internally and never overflows for moduli that fit the type.
+
+/// Integer types with digit and number-theory helpers.
+pub trait Integer: Copy + Sized {
+    /// Number of decimal digits, ignoring the sign. `0` has one digit.
+    fn digit_count(self) -> u32;
+
+    /// Appends the digits of `rhs`, e.g. `12.checked_concat(345) == Some(12345)`.
+    ///
+    /// `None` on overflow or if `rhs` is negative.
+    fn checked_concat(self, rhs: Self) -> Option<Self>;
+
+    /// Splits a number with an even count of digits into its left and right half,
+    /// e.g. `1024` into `(10, 24)`. `None` for odd digit counts and negative numbers.
+    fn split_digits(self) -> Option<(Self, Self)>;
+
+    /// Greatest common divisor, always non-negative. `None` if it does not fit the type,
+    /// which only happens for `gcd(MIN, 0)` of signed types.
+    fn checked_gcd(self, other: Self) -> Option<Self>;
+
+    /// Least common multiple, always non-negative. `None` on overflow.
+    fn checked_lcm(self, other: Self) -> Option<Self>;
+
+    /// `self^exp mod m`, in `0..m`. `None` if `m` is not positive.
+    fn checked_mod_pow(self, exp: u64, m: Self) -> Option<Self>;
+
+    /// The `x` in `0..m` with `self * x = 1 (mod m)`, if `self` and `m` are coprime and `m` is positive.
+    fn mod_inv(self, m: Self) -> Option<Self>;
+
+    fn concat(self, rhs: Self) -> Self {
+        self.checked_concat(rhs).expect("overflow in concat")
+    }
+
+    fn gcd(self, other: Self) -> Self {
+        self.checked_gcd(other).expect("overflow in gcd")
+    }
+
+    fn lcm(self, other: Self) -> Self {
+        self.checked_lcm(other).expect("overflow in lcm")
+    }
+
+    fn mod_pow(self, exp: u64, m: Self) -> Self {
+        self.checked_mod_pow(exp, m)
+            .expect("modulus must be positive")
+    }
+}
+
+/// Extended Euclid for signed types.
+pub trait ExtendedGcd: Sized {
+    /// Returns `(g, x, y)` with `g = gcd(a, b) = a*x + b*y`. `None` if any of them does not fit
+    /// the type, e.g. for `ext_gcd(MIN, 0)`.
+    fn checked_ext_gcd(self, other: Self) -> Option<(Self, Self, Self)>;
+
+    fn ext_gcd(self, other: Self) -> (Self, Self, Self) {
+        self.checked_ext_gcd(other).expect("overflow in ext_gcd")
+    }
+}
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn digit_count_u128(mut x: u128) -> u32 {
+    let mut count = 1;
+    while x >= 10 {
+        x /= 10;
+        count += 1;
+    }
+    count
+}
+
+fn mul_mod(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // double-and-add, so nothing exceeds 2m.
+    let (mut a, mut b, mut result) = (a % m, b % m, 0u128);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+fn add_mod(a: u128, b: u128, m: u128) -> u128 {
+    match a.checked_add(b) {
+        Some(sum) => sum % m,
+        None => a - (m - b),
+    }
+}
+
+fn pow_mod(base: u128, mut exp: u64, m: u128) -> u128 {
+    let mut base = base % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+fn inv_mod(a: u128, m: u128) -> Option<u128> {
+    // coefficients of `a` are tracked modulo `m`, so they never go negative.
+    let (mut r0, mut r1) = (a % m, m);
+    let (mut s0, mut s1) = (1 % m, 0);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, add_mod(s0, m - mul_mod(q, s1, m), m));
+    }
+    (r0 == 1 || m == 1).then_some(s0 % m)
+}
+
+fn crt_u128(congruences: impl IntoIterator<Item = (u128, u128)>) -> Option<(u128, u128)> {
+    let (mut r, mut m) = (0u128, 1u128);
+    for (r2, m2) in congruences {
+        if m2 == 0 {
+            return None;
+        }
+        let r2 = r2 % m2;
+        let g = gcd_u128(m, m2);
+        let diff = add_mod(r2, m2 - r % m2, m2);
+        if !diff.is_multiple_of(g) {
+            return None;
+        }
+
+        // solve r + m*k = r2 (mod m2) for k.
+        let m2g = m2 / g;
+        let k = mul_mod(diff / g, inv_mod(m / g, m2g)?, m2g);
+        let lcm = (m / g).checked_mul(m2)?;
+        r = add_mod(r, mul_mod(m, k, lcm), lcm);
+        m = lcm;
+    }
+    Some((r, m))
+}
+
+/// Solves a system of congruences `x = r (mod m)`, returning `(x, lcm of all m)` with `x` in `0..lcm`.
+///
+/// Moduli don't need to be coprime. `None` if the system has no solution, a modulus is not positive,
+/// or the combined modulus overflows.
+pub fn crt<T: Crt>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    T::crt(congruences)
+}
+
+/// Types supported by [`crt`].
+pub trait Crt: Sized {
+    fn crt(congruences: &[(Self, Self)]) -> Option<(Self, Self)>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn digit_count(self) -> u32 {
+                digit_count_u128(self as u128)
+            }
+
+            fn checked_concat(self, rhs: Self) -> Option<Self> {
+                (10 as $t)
+                    .checked_pow(rhs.digit_count())?
+                    .checked_mul(self)?
+                    .checked_add(rhs)
+            }
+
+            fn split_digits(self) -> Option<(Self, Self)> {
+                let count = self.digit_count();
+                if count % 2 == 1 {
+                    return None;
+                }
+                let pow10 = (10 as $t).pow(count / 2);
+                Some((self / pow10, self % pow10))
+            }
+
+            fn checked_gcd(self, other: Self) -> Option<Self> {
+                Some(gcd_u128(self as u128, other as u128) as $t)
+            }
+
+            fn checked_lcm(self, other: Self) -> Option<Self> {
+                if self == 0 || other == 0 {
+                    return Some(0);
+                }
+                (self / self.gcd(other)).checked_mul(other)
+            }
+
+            fn checked_mod_pow(self, exp: u64, m: Self) -> Option<Self> {
+                (m > 0).then(|| pow_mod(self as u128, exp, m as u128) as $t)
+            }
+
+            fn mod_inv(self, m: Self) -> Option<Self> {
+                if m == 0 {
+                    return None;
+                }
+                inv_mod(self as u128, m as u128).map(|x| x as $t)
+            }
+        }
+
+        impl Crt for $t {
+            fn crt(congruences: &[(Self, Self)]) -> Option<(Self, Self)> {
+                let (r, m) = crt_u128(congruences.iter().map(|&(r, m)| (r as u128, m as u128)))?;
+                Some((r.try_into().ok()?, m.try_into().ok()?))
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            fn digit_count(self) -> u32 {
+                digit_count_u128(self.unsigned_abs() as u128)
+            }
+
+            fn checked_concat(self, rhs: Self) -> Option<Self> {
+                if rhs < 0 {
+                    return None;
+                }
+                let shifted = (10 as $t).checked_pow(rhs.digit_count())?.checked_mul(self)?;
+                if self < 0 {
+                    shifted.checked_sub(rhs)
+                } else {
+                    shifted.checked_add(rhs)
+                }
+            }
+
+            fn split_digits(self) -> Option<(Self, Self)> {
+                if self < 0 {
+                    return None;
+                }
+                let count = self.digit_count();
+                if count % 2 == 1 {
+                    return None;
+                }
+                let pow10 = (10 as $t).pow(count / 2);
+                Some((self / pow10, self % pow10))
+            }
+
+            fn checked_gcd(self, other: Self) -> Option<Self> {
+                gcd_u128(self.unsigned_abs() as u128, other.unsigned_abs() as u128)
+                    .try_into()
+                    .ok()
+            }
+
+            fn checked_lcm(self, other: Self) -> Option<Self> {
+                if self == 0 || other == 0 {
+                    return Some(0);
+                }
+                let g = self.unsigned_abs().checked_gcd(other.unsigned_abs())?;
+                let lcm = (self.unsigned_abs() / g).checked_mul(other.unsigned_abs())?;
+                lcm.try_into().ok()
+            }
+
+            fn checked_mod_pow(self, exp: u64, m: Self) -> Option<Self> {
+                if m <= 0 {
+                    return None;
+                }
+                let base = self.rem_euclid(m) as u128;
+                Some(pow_mod(base, exp, m as u128) as $t)
+            }
+
+            fn mod_inv(self, m: Self) -> Option<Self> {
+                if m <= 0 {
+                    return None;
+                }
+                inv_mod(self.rem_euclid(m) as u128, m as u128).map(|x| x as $t)
+            }
+        }
+
+        impl ExtendedGcd for $t {
+            fn checked_ext_gcd(self, other: Self) -> Option<(Self, Self, Self)> {
+                let (mut r0, mut r1) = (self, other);
+                let (mut x0, mut x1): (Self, Self) = (1, 0);
+                let (mut y0, mut y1): (Self, Self) = (0, 1);
+                // stops as soon as `r1` divides `r0`, so the quotient that could overflow
+                // (`MIN / -1`) is never computed.
+                while r1 != 0 && r0.wrapping_rem(r1) != 0 {
+                    let q = r0 / r1;
+                    (r0, r1) = (r1, r0 % r1);
+                    (x0, x1) = (x1, x0.checked_sub(q.checked_mul(x1)?)?);
+                    (y0, y1) = (y1, y0.checked_sub(q.checked_mul(y1)?)?);
+                }
+                let (g, x, y) = if r1 == 0 { (r0, x0, y0) } else { (r1, x1, y1) };
+                if g < 0 {
+                    Some((g.checked_neg()?, x.checked_neg()?, y.checked_neg()?))
+                } else {
+                    Some((g, x, y))
+                }
+            }
+        }
+
+        impl Crt for $t {
+            fn crt(congruences: &[(Self, Self)]) -> Option<(Self, Self)> {
+                let mut unsigned = Vec::with_capacity(congruences.len());
+                for &(r, m) in congruences {
+                    if m <= 0 {
+                        return None;
+                    }
+                    unsigned.push((r.rem_euclid(m) as u128, m as u128));
+                }
+                let (r, m) = crt_u128(unsigned)?;
+                Some((r.try_into().ok()?, m.try_into().ok()?))
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(0u64.digit_count(), 1);
+        assert_eq!(9u64.digit_count(), 1);
+        assert_eq!(10u64.digit_count(), 2);
+        assert_eq!(u128::MAX.digit_count(), 39);
+        assert_eq!((-1234i64).digit_count(), 4);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(12u64.concat(345), 12345);
+        assert_eq!((-12i64).checked_concat(3), Some(-123));
+        assert_eq!(12i64.checked_concat(-3), None);
+        assert_eq!(u64::MAX.checked_concat(1), None);
+        assert_eq!(
+            1u128.checked_concat(u64::MAX as u128),
+            Some(118446744073709551615)
+        );
+    }
+
+    #[test]
+    fn test_split_digits() {
+        assert_eq!(123u64.split_digits(), None);
+        assert_eq!(123023u64.split_digits(), Some((123, 23)));
+        assert_eq!(1000i64.split_digits(), Some((10, 0)));
+        assert_eq!((-10i64).split_digits(), None);
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(12u64.gcd(18), 6);
+        assert_eq!((-12i64).gcd(18), 6);
+        assert_eq!(0u64.gcd(5), 5);
+        assert_eq!(4u64.lcm(6), 12);
+        assert_eq!((-4i64).lcm(6), 12);
+        assert_eq!(u64::MAX.checked_lcm(u64::MAX - 1), None);
+        assert_eq!(i64::MIN.checked_gcd(0), None);
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        let (g, x, y) = 240i64.ext_gcd(46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        let (g, x, y) = (-15i128).ext_gcd(10);
+        assert_eq!(g, 5);
+        assert_eq!(-15 * x + 10 * y, 5);
+
+        assert_eq!(i64::MIN.checked_ext_gcd(0), None);
+        assert_eq!(0i64.checked_ext_gcd(i64::MIN), None);
+        assert_eq!(i64::MIN.checked_ext_gcd(-1), Some((1, 0, -1)));
+        let (g, x, y) = i64::MIN.ext_gcd(6);
+        assert_eq!(g, 2);
+        assert_eq!(i128::from(i64::MIN) * i128::from(x) + 6 * i128::from(y), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow in ext_gcd")]
+    fn test_ext_gcd_overflow() {
+        i64::MIN.ext_gcd(0);
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(3u64.mod_pow(200, 1_000_000_007), 136_318_165);
+        assert_eq!((-2i64).mod_pow(3, 5), 2);
+        assert_eq!(5u64.checked_mod_pow(3, 0), None);
+        assert_eq!(2u128.mod_pow(127, u128::MAX), 1 << 127);
+
+        assert_eq!(3u64.mod_inv(11), Some(4));
+        assert_eq!((-3i64).mod_inv(11), Some(7));
+        assert_eq!(4u64.mod_inv(8), None);
+        let m = u128::MAX - 158; // a large prime
+        assert_eq!(mul_mod(12345u128.mod_inv(m).unwrap(), 12345, m), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1i64, 4), (3, 6)]), Some((3, 12)));
+        assert_eq!(crt(&[(1u64, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(0u8, 16), (0, 17)]), None);
+        assert_eq!(crt::<u64>(&[]), Some((0, 1)));
+    }
+}
@@ -0,0 +1,220 @@
+//! Search for operators that combine a list of operands into a target value.
+//!
+//! Expressions are evaluated strictly left to right, ignoring precedence, so `81 * 40 + 27` is
+//! `(81 * 40) + 27`. The search runs backwards from the target: the inverse of an operator peels
+//! the last operand off, which prunes most branches long before the first operand is reached.
+use std::fmt::{self, Display};
+
+use crate::num::Integer;
+
+/// A binary operator with its inverse.
+#[derive(Debug, Clone, Copy)]
+pub struct Operator {
+    pub symbol: &'static str,
+    /// `lhs op rhs`, `None` on overflow.
+    pub forward: fn(u64, u64) -> Option<u64>,
+    /// The `lhs` with `lhs op rhs == result`, if there is one.
+    pub inverse: fn(u64, u64) -> Option<u64>,
+}
+
+pub const ADD: Operator = Operator {
+    symbol: "+",
+    forward: u64::checked_add,
+    inverse: u64::checked_sub,
+};
+
+pub const MUL: Operator = Operator {
+    symbol: "*",
+    forward: u64::checked_mul,
+    inverse: |result, rhs| (rhs != 0 && result.is_multiple_of(rhs)).then(|| result / rhs),
+};
+
+/// Appends the digits of `rhs`, so `12 || 345` is `12345`.
+pub const CONCAT: Operator = Operator {
+    symbol: "||",
+    forward: u64::checked_concat,
+    inverse: |result, rhs| {
+        let pow10 = 10u64.checked_pow(rhs.digit_count())?;
+        (result % pow10 == rhs).then(|| result / pow10)
+    },
+};
+
+/// Operands joined by operators, e.g. `81 * 40 + 27`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    pub operands: Vec<u64>,
+    /// `operators[i]` joins `operands[i]` and `operands[i + 1]`.
+    pub operators: Vec<&'static str>,
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, operand) in self.operands.iter().enumerate() {
+            if i > 0 {
+                write!(f, " {} ", self.operators[i - 1])?;
+            }
+            write!(f, "{operand}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Decides which targets can be built from operands with a set of operators.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    operators: Vec<Operator>,
+}
+
+impl Solver {
+    pub fn new(operators: &[Operator]) -> Self {
+        Self {
+            operators: operators.to_vec(),
+        }
+    }
+
+    /// Adds another operator to try.
+    #[must_use]
+    pub fn with(mut self, operator: Operator) -> Self {
+        self.operators.push(operator);
+        self
+    }
+
+    pub fn is_reachable(&self, target: u64, operands: &[u64]) -> bool {
+        self.search(target, operands, &mut Vec::new())
+    }
+
+    /// An expression over `operands` that evaluates to `target`, if there is one.
+    pub fn solve(&self, target: u64, operands: &[u64]) -> Option<Expression> {
+        let mut used = Vec::new();
+        if !self.search(target, operands, &mut used) {
+            return None;
+        }
+        used.reverse();
+        Some(Expression {
+            operands: operands.to_vec(),
+            operators: used.into_iter().map(|op| op.symbol).collect(),
+        })
+    }
+
+    // `used` collects the operators back to front, from the last operand on.
+    fn search(&self, target: u64, operands: &[u64], used: &mut Vec<Operator>) -> bool {
+        let Some((&last, rest)) = operands.split_last() else {
+            return false;
+        };
+        if rest.is_empty() {
+            return target == last;
+        }
+
+        for &op in &self.operators {
+            let Some(lhs) = (op.inverse)(target, last) else {
+                // `rhs` can absorb `lhs`, e.g. `x * 0 == 0` for every `x`, which leaves the
+                // inverse without a single answer: any value of the remaining operands will do.
+                if (op.forward)(0, last) == Some(target) && (op.forward)(1, last) == Some(target) {
+                    used.push(op);
+                    let accept = |value| (op.forward)(value, last) == Some(target);
+                    if self.search_forward(rest[0], &rest[1..], &accept, used) {
+                        return true;
+                    }
+                    used.pop();
+                }
+                continue;
+            };
+            // guards against inverses that are not exact, e.g. of user-defined operators.
+            if (op.forward)(lhs, last) != Some(target) {
+                continue;
+            }
+            used.push(op);
+            if self.search(lhs, rest, used) {
+                return true;
+            }
+            used.pop();
+        }
+
+        false
+    }
+
+    /// Evaluates `value` followed by `operands` front to back, until a result is accepted.
+    // pushes the operators while unwinding, so `used` stays back to front.
+    fn search_forward(
+        &self,
+        value: u64,
+        operands: &[u64],
+        accept: &dyn Fn(u64) -> bool,
+        used: &mut Vec<Operator>,
+    ) -> bool {
+        let Some((&next, rest)) = operands.split_first() else {
+            return accept(value);
+        };
+        for &op in &self.operators {
+            let Some(value) = (op.forward)(value, next) else {
+                continue;
+            };
+            if self.search_forward(value, rest, accept, used) {
+                used.push(op);
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reachable() {
+        let solver = Solver::new(&[ADD, MUL]);
+        assert!(solver.is_reachable(190, &[10, 19]));
+        assert!(solver.is_reachable(3267, &[81, 40, 27]));
+        assert!(!solver.is_reachable(156, &[15, 6]));
+        assert!(!solver.is_reachable(1, &[]));
+
+        let solver = solver.with(CONCAT);
+        assert!(solver.is_reachable(156, &[15, 6]));
+        assert!(solver.is_reachable(7290, &[6, 8, 6, 15]));
+    }
+
+    #[test]
+    fn test_witness() {
+        let solver = Solver::new(&[ADD, MUL, CONCAT]);
+        let expression = solver.solve(3267, &[81, 40, 27]).unwrap();
+        assert_eq!(expression.to_string(), "81 * 40 + 27");
+        assert_eq!(
+            solver.solve(7290, &[6, 8, 6, 15]).unwrap().to_string(),
+            "6 * 8 || 6 * 15"
+        );
+        assert_eq!(solver.solve(5, &[5]).unwrap().to_string(), "5");
+        assert_eq!(solver.solve(161011, &[16, 10, 13]), None);
+    }
+
+    #[test]
+    fn test_zero_operand() {
+        let solver = Solver::new(&[ADD, MUL]);
+        assert!(solver.is_reachable(0, &[5, 0]));
+        assert!(!solver.is_reachable(1, &[5, 0]));
+        assert_eq!(solver.solve(0, &[5, 0]).unwrap().to_string(), "5 * 0");
+        assert_eq!(
+            solver.solve(0, &[2, 3, 0, 4]).unwrap().to_string(),
+            "2 + 3 * 0 * 4"
+        );
+        assert_eq!(
+            solver.solve(7, &[2, 3, 0, 7]).unwrap().to_string(),
+            "2 + 3 * 0 + 7"
+        );
+    }
+
+    #[test]
+    fn test_custom_operator() {
+        const SUB: Operator = Operator {
+            symbol: "-",
+            forward: u64::checked_sub,
+            inverse: u64::checked_add,
+        };
+        let solver = Solver::new(&[SUB]);
+        assert_eq!(
+            solver.solve(3, &[10, 4, 3]).unwrap().to_string(),
+            "10 - 4 - 3"
+        );
+    }
+}
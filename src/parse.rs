@@ -0,0 +1,287 @@
+//! Fallible helpers for reading puzzle input.
+//!
+//! Every helper returns a [`ParseError`] pointing at the offending text instead of panicking.
+//! Helpers that work on a single line report line 1; [`parse_lines`] and [`Section::parse_lines`]
+//! shift those errors to the line they actually came from.
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending text.
+    pub line: usize,
+    /// 1-based column (in bytes) of the offending text.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+impl ParseError {
+    /// An error at the start of the first line.
+    pub fn new(message: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            line: 1,
+            column: 1,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Moves the error to `column` (1-based) of its line.
+    #[must_use]
+    pub fn at_column(mut self, column: usize) -> Self {
+        self.column = column;
+        self
+    }
+
+    /// Shifts the error by `lines`, for input that started further down.
+    #[must_use]
+    pub fn shifted(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} (at \"{}\")",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a single value, ignoring surrounding whitespace.
+pub fn int<T: FromStr>(text: &str) -> Result<T> {
+    let trimmed = text.trim_start();
+    let offset = text.len() - trimmed.len();
+    trimmed
+        .trim_end()
+        .parse()
+        .map_err(|_| ParseError::new("invalid number", trimmed.trim_end()).at_column(offset + 1))
+}
+
+/// Extracts all integers from `line`, skipping everything in between.
+///
+/// A `-` directly before the digits makes the number negative, unless it follows a letter or digit,
+/// so `x=-3` gives `-3` but `10-20` gives `10` and `20`.
+pub fn ints<T: FromStr>(line: &str) -> Result<Vec<T>> {
+    let bytes = line.as_bytes();
+    let mut values = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let mut start = i;
+        if start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let text = &line[start..i];
+        let value = text
+            .parse()
+            .map_err(|_| ParseError::new("invalid number", text).at_column(start + 1))?;
+        values.push(value);
+    }
+
+    Ok(values)
+}
+
+/// Parses every field of `line` with [`int`], e.g. `int_fields(line, line.split(','))`.
+///
+/// Unlike [`ints`], anything that isn't a number is an error. `fields` must be slices of `line`,
+/// so that errors point at the column of the offending field.
+pub fn int_fields<'a, T: FromStr>(
+    line: &'a str,
+    fields: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<T>> {
+    fields
+        .into_iter()
+        .map(|field| {
+            let offset = (field.as_ptr() as usize)
+                .checked_sub(line.as_ptr() as usize)
+                .filter(|&offset| offset <= line.len())
+                .unwrap_or(0);
+            int(field).map_err(|e| {
+                let column = e.column + offset;
+                e.at_column(column)
+            })
+        })
+        .collect()
+}
+
+/// Splits `line` at the first `separator`.
+pub fn split_once<'a>(line: &'a str, separator: &str) -> Result<(&'a str, &'a str)> {
+    line.split_once(separator)
+        .ok_or_else(|| ParseError::new(format!("expected \"{separator}\""), line))
+}
+
+/// Splits a `key: values` line and extracts all integers from the values.
+pub fn key_values<T: FromStr>(line: &str) -> Result<(&str, Vec<T>)> {
+    let (key, values) = split_once(line, ":")?;
+    let offset = key.len() + 1;
+    let values = ints(values).map_err(|e| {
+        let column = e.column + offset;
+        e.at_column(column)
+    })?;
+    Ok((key.trim(), values))
+}
+
+/// Parses every line of `input` with `f`.
+pub fn parse_lines<'a, T>(input: &'a str, f: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+    Section {
+        first_line: 1,
+        text: input,
+    }
+    .parse_lines(f)
+}
+
+/// A block of lines, separated from the rest of the input by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// 1-based line of the input where this section starts.
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines()
+    }
+
+    /// Parses every line of the section with `f`, reporting errors relative to the whole input.
+    pub fn parse_lines<T>(&self, mut f: impl FnMut(&'a str) -> Result<T>) -> Result<Vec<T>> {
+        self.lines()
+            .enumerate()
+            .map(|(i, line)| f(line).map_err(|e| e.shifted(self.first_line - 1 + i)))
+            .collect()
+    }
+}
+
+/// Splits `input` on blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        match (start, is_blank) {
+            (None, false) => start = Some((i + 1, offset)),
+            (Some((first_line, begin)), true) => {
+                sections.push(Section {
+                    first_line,
+                    text: &input[begin..offset],
+                });
+                start = None;
+            }
+            _ => {}
+        }
+        offset += line.len();
+    }
+
+    if let Some((first_line, begin)) = start {
+        sections.push(Section {
+            first_line,
+            text: &input[begin..],
+        });
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints::<i64>("x=-3, y=10..20").unwrap(), vec![-3, 10, 20]);
+        assert_eq!(ints::<u32>("10-20 a-5").unwrap(), vec![10, 20, 5]);
+        assert_eq!(ints::<i32>("no numbers").unwrap(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_int_fields() {
+        let line = "1, 2,3";
+        assert_eq!(
+            int_fields::<u8>(line, line.split(',')).unwrap(),
+            vec![1, 2, 3]
+        );
+
+        let line = "4   x3";
+        let err = int_fields::<i32>(line, line.split_ascii_whitespace()).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (5, "x3"));
+
+        let line = "1,,2";
+        let err = int_fields::<i32>(line, line.split(',')).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, ""));
+    }
+
+    #[test]
+    fn test_ints_error() {
+        let err = ints::<u8>("1 2 300").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.text, "300");
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 5: invalid number (at \"300\")"
+        );
+    }
+
+    #[test]
+    fn test_int() {
+        assert_eq!(int::<u32>(" 42 ").unwrap(), 42);
+        let err = int::<u32>("  4x").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "4x"));
+    }
+
+    #[test]
+    fn test_key_values() {
+        let (key, values) = key_values::<u64>("190: 10 19").unwrap();
+        assert_eq!((key, values), ("190", vec![10, 19]));
+
+        let err = key_values::<u8>("a: 1 999").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "999"));
+        assert!(key_values::<u8>("a 1").is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "a\nb\n\n\nc\n";
+        let sections = sections(input);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].text, "a\nb\n");
+        assert_eq!(sections[1].first_line, 5);
+        assert_eq!(sections[1].text, "c\n");
+    }
+
+    #[test]
+    fn test_parse_lines_reports_line() {
+        let input = "1|2\n\n1,2\n3,300\n";
+        let err = sections(input)[1].parse_lines(ints::<u8>).unwrap_err();
+        assert_eq!((err.line, err.column), (4, 3));
+        assert_eq!(err.text, "300");
+
+        let err = parse_lines(input, |line| split_once(line, "|")).unwrap_err();
+        assert_eq!(err.line, 2);
+    }
+}
@@ -0,0 +1,368 @@
+//! Points, offsets and compass directions for coordinate puzzles.
+//!
+//! Coordinates follow the same `(row, col)` convention as [`crate::grid::Grid`]: rows grow downwards,
+//! so [`Dir4::Up`] is `(-1, 0)`.
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::grid::Grid;
+
+/// A position on a 2D plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+/// A difference between two [`Point`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    pub fn manhattan(self, other: Point) -> i64 {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Point) -> i64 {
+        (other - self).chebyshev()
+    }
+
+    /// The point as an unsigned `(row, col)` pair, if both coordinates are non-negative.
+    pub fn to_unsigned(self) -> Option<(usize, usize)> {
+        Some((
+            usize::try_from(self.row).ok()?,
+            usize::try_from(self.col).ok()?,
+        ))
+    }
+
+    /// The 4 orthogonal neighbours, in [`Dir4::ALL`] order.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir)
+    }
+
+    /// The 8 orthogonal and diagonal neighbours, in [`Dir8::ALL`] order.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir)
+    }
+}
+
+impl Vec2 {
+    pub const fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    pub fn manhattan(self) -> i64 {
+        self.row.abs() + self.col.abs()
+    }
+
+    pub fn chebyshev(self) -> i64 {
+        self.row.abs().max(self.col.abs())
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((row, col): (i64, i64)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self {
+            row: row as i64,
+            col: col as i64,
+        }
+    }
+}
+
+impl From<(i64, i64)> for Vec2 {
+    fn from((row, col): (i64, i64)) -> Self {
+        Self { row, col }
+    }
+}
+
+impl<V: Into<Vec2>> Add<V> for Point {
+    type Output = Point;
+
+    fn add(self, v: V) -> Point {
+        let v = v.into();
+        Point::new(self.row + v.row, self.col + v.col)
+    }
+}
+
+impl<V: Into<Vec2>> AddAssign<V> for Point {
+    fn add_assign(&mut self, v: V) {
+        *self = *self + v;
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vec2) -> Point {
+        Point::new(self.row - v.row, self.col - v.col)
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, v: Vec2) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, other: Point) -> Vec2 {
+        Vec2::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.row + v.row, self.col + v.col)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.row - v.row, self.col - v.col)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, k: i64) -> Vec2 {
+        Vec2::new(self.row * k, self.col * k)
+    }
+}
+
+/// Integer division of both components, e.g. to reduce a step by its gcd.
+impl Div<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn div(self, k: i64) -> Vec2 {
+        Vec2::new(self.row / k, self.col / k)
+    }
+}
+
+/// One of the 4 orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting at [`Dir4::Up`].
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Dir4::Up => Vec2::new(-1, 0),
+            Dir4::Right => Vec2::new(0, 1),
+            Dir4::Down => Vec2::new(1, 0),
+            Dir4::Left => Vec2::new(0, -1),
+        }
+    }
+}
+
+/// Parses arrows (`^>v<`) and letters (`UDLR`).
+impl TryFrom<char> for Dir4 {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, char> {
+        match c {
+            '^' | 'U' => Ok(Dir4::Up),
+            '>' | 'R' => Ok(Dir4::Right),
+            'v' | 'D' => Ok(Dir4::Down),
+            '<' | 'L' => Ok(Dir4::Left),
+            _ => Err(c),
+        }
+    }
+}
+
+impl From<Dir4> for Vec2 {
+    fn from(dir: Dir4) -> Self {
+        dir.delta()
+    }
+}
+
+/// One of the 8 compass directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting at [`Dir8::N`].
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    /// Turns clockwise by 45 degrees.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns counter-clockwise by 45 degrees.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn delta(self) -> Vec2 {
+        match self {
+            Dir8::N => Vec2::new(-1, 0),
+            Dir8::NE => Vec2::new(-1, 1),
+            Dir8::E => Vec2::new(0, 1),
+            Dir8::SE => Vec2::new(1, 1),
+            Dir8::S => Vec2::new(1, 0),
+            Dir8::SW => Vec2::new(1, -1),
+            Dir8::W => Vec2::new(0, -1),
+            Dir8::NW => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::ALL[dir as usize * 2]
+    }
+}
+
+impl From<Dir8> for Vec2 {
+    fn from(dir: Dir8) -> Self {
+        dir.delta()
+    }
+}
+
+/// Lookup by [`Point`], returning `None` outside the bounds.
+///
+/// Implemented for [`Grid`] and for nested vectors like `Vec<Vec<char>>`.
+pub trait At<T> {
+    fn at(&self, p: Point) -> Option<&T>;
+}
+
+impl<T> At<T> for [Vec<T>] {
+    fn at(&self, p: Point) -> Option<&T> {
+        let (row, col) = p.to_unsigned()?;
+        self.get(row)?.get(col)
+    }
+}
+
+impl<T> At<T> for Vec<Vec<T>> {
+    fn at(&self, p: Point) -> Option<&T> {
+        self.as_slice().at(p)
+    }
+}
+
+impl<T> At<T> for Grid<T> {
+    fn at(&self, p: Point) -> Option<&T> {
+        self.get(p.to_unsigned()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(b - a, Vec2::new(3, -4));
+        assert_eq!(a + (b - a) * 2, Point::new(7, -6));
+        assert_eq!(a - (b - a), Point::new(-2, 6));
+        assert_eq!(-Vec2::new(3, -4) / 1, Vec2::new(-3, 4));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn test_dir4() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir4::try_from('v'), Ok(Dir4::Down));
+        assert_eq!(Dir4::try_from('L'), Ok(Dir4::Left));
+        assert_eq!(Dir4::try_from('x'), Err('x'));
+        assert_eq!(Point::new(0, 0) + Dir4::Up, Point::new(-1, 0));
+    }
+
+    #[test]
+    fn test_dir8() {
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+        assert_eq!(Dir8::NE.opposite(), Dir8::SW);
+        assert_eq!(Dir8::from(Dir4::Left), Dir8::W);
+        assert_eq!(Point::new(0, 0).neighbours8().count(), 8);
+    }
+
+    #[test]
+    fn test_at() {
+        let field: Vec<Vec<char>> = vec!["ab".chars().collect(), "cd".chars().collect()];
+        assert_eq!(field.at(Point::new(1, 0)), Some(&'c'));
+        assert_eq!(field.at(Point::new(-1, 0)), None);
+        assert_eq!(field.at(Point::new(0, 2)), None);
+
+        let grid = Grid::parse("ab\ncd\n");
+        assert_eq!(grid.at(Point::new(1, 1)), Some(&'d'));
+    }
+}
@@ -0,0 +1,201 @@
+//! A streaming scanner for instructions hidden in corrupted memory.
+//!
+//! The scanner walks the input byte by byte in a single pass and yields only instructions that
+//! match the grammar exactly: `mul(A,B)` with 1 to 3 digits per operand, `do()` and `don't()`.
+//! Every instruction starts with a byte (`m` or `d`) that never occurs later in any instruction,
+//! so after a mismatch the scan simply restarts at the offending byte.
+
+/// An instruction found in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keyword {
+    Mul,
+    Do,
+    Dont,
+}
+
+impl Keyword {
+    fn text(self) -> &'static [u8] {
+        match self {
+            Keyword::Mul => b"mul(",
+            Keyword::Do => b"do()",
+            Keyword::Dont => b"don't()",
+        }
+    }
+}
+
+/// Operands of `mul` have at most this many digits.
+const MAX_DIGITS: u8 = 3;
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Start,
+    /// The first `matched` bytes of `keyword` were seen.
+    Keyword {
+        keyword: Keyword,
+        matched: usize,
+    },
+    /// Inside the operands of `mul`, `lhs` is set once the `,` was seen.
+    Operand {
+        lhs: Option<u32>,
+        value: u32,
+        digits: u8,
+    },
+}
+
+/// Iterates over the instructions in a byte string.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    state: State,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self::from_bytes(input.as_bytes())
+    }
+
+    pub fn from_bytes(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            state: State::Start,
+        }
+    }
+
+    /// Feeds one byte, returning the next state and the instruction it completes, if any.
+    /// `None` means the byte doesn't continue the current instruction.
+    // inlined into the scan loop, the state stays in registers: about twice as fast.
+    #[inline(always)]
+    fn step(state: State, byte: u8) -> Option<(State, Option<Instruction>)> {
+        let next = match state {
+            State::Start => match byte {
+                b'm' => State::Keyword {
+                    keyword: Keyword::Mul,
+                    matched: 1,
+                },
+                b'd' => State::Keyword {
+                    keyword: Keyword::Do,
+                    matched: 1,
+                },
+                _ => return None,
+            },
+            // `do(` and `don` share their prefix, so `n` switches from one to the other.
+            State::Keyword {
+                keyword: Keyword::Do,
+                matched: 2,
+            } if byte == b'n' => State::Keyword {
+                keyword: Keyword::Dont,
+                matched: 3,
+            },
+            State::Keyword { keyword, matched } if keyword.text()[matched] == byte => {
+                let matched = matched + 1;
+                if matched < keyword.text().len() {
+                    State::Keyword { keyword, matched }
+                } else {
+                    match keyword {
+                        Keyword::Mul => State::Operand {
+                            lhs: None,
+                            value: 0,
+                            digits: 0,
+                        },
+                        Keyword::Do => return Some((State::Start, Some(Instruction::Do))),
+                        Keyword::Dont => return Some((State::Start, Some(Instruction::Dont))),
+                    }
+                }
+            }
+            State::Operand { lhs, value, digits } => match (byte, lhs) {
+                (b'0'..=b'9', _) if digits < MAX_DIGITS => State::Operand {
+                    lhs,
+                    value: value * 10 + (byte - b'0') as u32,
+                    digits: digits + 1,
+                },
+                (b',', None) if digits > 0 => State::Operand {
+                    lhs: Some(value),
+                    value: 0,
+                    digits: 0,
+                },
+                (b')', Some(lhs)) if digits > 0 => {
+                    return Some((State::Start, Some(Instruction::Mul(lhs, value))))
+                }
+                _ => return None,
+            },
+            State::Keyword { .. } => return None,
+        };
+        Some((next, None))
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Instruction;
+
+    fn next(&mut self) -> Option<Instruction> {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            // between instructions, most bytes can't start one and are skipped without a step.
+            if let State::Start = self.state {
+                if byte != b'm' && byte != b'd' {
+                    self.pos += 1;
+                    continue;
+                }
+            }
+
+            let step = Self::step(self.state, byte).or_else(|| match self.state {
+                State::Start => None,
+                _ => Self::step(State::Start, byte),
+            });
+            self.pos += 1;
+
+            let (state, instruction) = step.unwrap_or((State::Start, None));
+            self.state = state;
+            if instruction.is_some() {
+                return instruction;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Instruction::*;
+
+    fn instructions(input: &str) -> Vec<Instruction> {
+        Scanner::new(input).collect()
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            instructions(
+                "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"
+            ),
+            vec![Mul(2, 4), Dont, Mul(5, 5), Mul(11, 8), Do, Mul(8, 5)]
+        );
+    }
+
+    #[test]
+    fn test_exact_grammar() {
+        assert_eq!(
+            instructions("mul(123,4)mul(1234,5)mul(6,7890)"),
+            vec![Mul(123, 4)]
+        );
+        assert_eq!(instructions("mul(,1)mul(1,)mul( 1,2)mul(1,2"), vec![]);
+        assert_eq!(instructions("do(don't()dont()do())"), vec![Dont, Do]);
+    }
+
+    #[test]
+    fn test_restart_after_mismatch() {
+        assert_eq!(
+            instructions("mmul(1,2)mumul(3,4)mul(5,mul(6,7)ddo()"),
+            vec![Mul(1, 2), Mul(3, 4), Mul(6, 7), Do]
+        );
+    }
+}
@@ -72,6 +72,27 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
+/// Downloads only the puzzle description, leaving the input file untouched.
+pub fn download_puzzle(day: Day) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(day);
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            "--puzzle-only".into(),
+            "--puzzle-file".into(),
+            puzzle_path.to_string(),
+        ],
+        day,
+    );
+
+    let output = call_aoc_cli(&args)?;
+    println!("---");
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(output)
+}
+
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
@@ -88,7 +109,7 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
@@ -0,0 +1,418 @@
+/// Interactive calendar view of all days, drawn with plain ANSI escape codes.
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::template::timings::Timings;
+use crate::template::watch::ANSI_CLEAR_SCREEN;
+use crate::template::{
+    all_days, aoc_cli, Day, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
+
+use super::{solve, time};
+
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_REVERSE: &str = "\x1b[7m";
+const ANSI_HIDE_CURSOR: &str = "\x1b[?25l";
+const ANSI_SHOW_CURSOR: &str = "\x1b[?25h";
+
+const STARS_MARKER: &str = "<!--- advent_readme_stars table --->";
+
+const COLUMNS: usize = 5;
+const CELL_WIDTH: usize = 13;
+
+/// Outcome of the last `cargo test` run for a day during this session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExampleStatus {
+    Unknown,
+    Passed,
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayStatus {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub has_input: bool,
+    pub examples: ExampleStatus,
+    pub stars: u8,
+    pub total_nanos: Option<f64>,
+}
+
+impl DayStatus {
+    fn collect(day: Day, timings: &Timings, stars: &HashMap<Day, u8>) -> Self {
+        let has_input = fs::metadata(format!("data/inputs/{day}.txt"))
+            .map(|m| m.len() > 0)
+            .unwrap_or(false);
+
+        Self {
+            day,
+            scaffolded: Path::new(&format!("src/bin/{day}.rs")).exists(),
+            has_input,
+            examples: ExampleStatus::Unknown,
+            stars: stars.get(&day).copied().unwrap_or(0),
+            total_nanos: timings
+                .data
+                .iter()
+                .find(|t| t.day == day)
+                .map(|t| t.total_nanos),
+        }
+    }
+}
+
+/// Reads the star count per day from the table maintained by the `readme-stars` workflow.
+pub fn parse_stars(readme: &str) -> HashMap<Day, u8> {
+    let mut stars = HashMap::new();
+
+    let Some(table) = readme.split(STARS_MARKER).nth(1) else {
+        return stars;
+    };
+
+    for line in table.lines() {
+        let Some(rest) = line.strip_prefix("| [Day ") else {
+            continue;
+        };
+
+        let day = rest
+            .split(']')
+            .next()
+            .and_then(|d| d.trim().parse::<Day>().ok());
+
+        if let Some(day) = day {
+            // `count()` is at most 2 here, the cast can not truncate.
+            #[allow(clippy::cast_possible_truncation)]
+            let count = line.matches('⭐').count() as u8;
+            stars.insert(day, count);
+        }
+    }
+
+    stars
+}
+
+fn read_stars() -> HashMap<Day, u8> {
+    fs::read_to_string("README.md")
+        .map(|readme| parse_stars(&readme))
+        .unwrap_or_default()
+}
+
+fn flag(label: &str, color: &str) -> String {
+    format!("{color}{label}{ANSI_RESET}")
+}
+
+fn format_millis(nanos: f64) -> String {
+    format!("{:.2}ms", nanos / 1_000_000_f64)
+}
+
+/// Renders the lines of a single calendar cell, padded to `CELL_WIDTH`.
+fn render_cell(status: &DayStatus, selected: bool) -> [String; 3] {
+    let stars = match status.stars {
+        0 => "  ".to_string(),
+        n => "★".repeat(n.into()),
+    };
+    let header = format!(
+        " Day {:<width$}{stars} ",
+        status.day.to_string(),
+        width = CELL_WIDTH - 6 - stars.chars().count()
+    );
+    let header = if selected {
+        format!("{ANSI_REVERSE}{ANSI_BOLD}{header}{ANSI_RESET}")
+    } else {
+        format!("{ANSI_BOLD}{header}{ANSI_RESET}")
+    };
+
+    let on_off = |on: bool| if on { ANSI_GREEN } else { ANSI_DIM };
+    let examples = match status.examples {
+        ExampleStatus::Unknown => flag("E", ANSI_DIM),
+        ExampleStatus::Passed => flag("E", ANSI_GREEN),
+        ExampleStatus::Failed => flag("E", ANSI_RED),
+    };
+    // 6 visible chars: `S I E` plus the leading space.
+    let flags = format!(
+        " {} {} {}{}",
+        flag("S", on_off(status.scaffolded)),
+        flag("I", on_off(status.has_input)),
+        examples,
+        " ".repeat(CELL_WIDTH - 6)
+    );
+
+    let timing = status
+        .total_nanos
+        .map_or_else(|| "-".to_string(), format_millis);
+    let timing = format!(
+        " {ANSI_ITALIC}{timing:<width$}{ANSI_RESET}",
+        width = CELL_WIDTH - 1
+    );
+
+    [header, flags, timing]
+}
+
+/// Renders the full calendar with `selected` highlighted.
+pub fn render(statuses: &[DayStatus], selected: usize) -> String {
+    let border = format!(
+        "+{}",
+        format!("{}+", "-".repeat(CELL_WIDTH)).repeat(COLUMNS)
+    );
+    let mut out = String::new();
+
+    out.push_str(&format!("{ANSI_BOLD}🎄 Advent of Code{ANSI_RESET}\n"));
+    out.push_str(&border);
+    out.push('\n');
+
+    for (row, chunk) in statuses.chunks(COLUMNS).enumerate() {
+        let cells: Vec<[String; 3]> = chunk
+            .iter()
+            .enumerate()
+            .map(|(col, status)| render_cell(status, row * COLUMNS + col == selected))
+            .collect();
+
+        for line in 0..3 {
+            out.push('|');
+            for cell in &cells {
+                out.push_str(&cell[line]);
+                out.push('|');
+            }
+            out.push('\n');
+        }
+
+        out.push_str(&border);
+        out.push('\n');
+    }
+
+    out.push_str(&format!(
+        "{ANSI_DIM}S: scaffolded  I: has input  E: examples pass (after `t`)  ★: stars{ANSI_RESET}\n"
+    ));
+    out.push_str(
+        "arrows/hjkl: move  s: solve  t: test  b: bench  o: open puzzle  r: refresh  q: quit\n",
+    );
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Char(u8),
+}
+
+/// Puts the terminal into raw mode via `stty` for the lifetime of the value.
+struct RawMode {
+    saved: Option<String>,
+}
+
+impl RawMode {
+    fn enable() -> Self {
+        let saved = stty(&["-g"]).map(|s| s.trim().to_string());
+        if saved.is_some() {
+            stty(&["raw", "-echo"]);
+        }
+        print!("{ANSI_HIDE_CURSOR}");
+        Self { saved }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if let Some(saved) = &self.saved {
+            stty(&[saved]);
+        }
+        print!("{ANSI_SHOW_CURSOR}");
+        let _ = io::stdout().flush();
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty").ok()?)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        None
+    }
+}
+
+fn read_key(stdin: &mut impl Read) -> Option<Key> {
+    let mut buf = [0u8; 1];
+    stdin.read_exact(&mut buf).ok()?;
+
+    if buf[0] != 0x1b {
+        return Some(Key::Char(buf[0]));
+    }
+
+    let mut seq = [0u8; 2];
+    stdin.read_exact(&mut seq).ok()?;
+    match seq {
+        [b'[', b'A'] => Some(Key::Up),
+        [b'[', b'B'] => Some(Key::Down),
+        [b'[', b'C'] => Some(Key::Right),
+        [b'[', b'D'] => Some(Key::Left),
+        _ => Some(Key::Char(0)),
+    }
+}
+
+fn wait_for_key(stdin: &mut impl Read) {
+    println!();
+    println!("{ANSI_ITALIC}Press any key to return to the dashboard.{ANSI_RESET}");
+    let _raw = RawMode::enable();
+    read_key(stdin);
+}
+
+fn run_tests(day: Day) -> ExampleStatus {
+    let status = Command::new("cargo")
+        .args(["test", "--bin", &day.to_string()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(s) if s.success() => ExampleStatus::Passed,
+        _ => ExampleStatus::Failed,
+    }
+}
+
+fn open_puzzle(day: Day) {
+    let url = match aoc_cli::get_year() {
+        Some(year) => format!("https://adventofcode.com/{year}/day/{}", day.into_inner()),
+        None => format!("https://adventofcode.com/day/{}", day.into_inner()),
+    };
+
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+
+    let opened = Command::new(opener)
+        .arg(&url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success());
+
+    if !opened {
+        println!("Could not open a browser. The puzzle is at {url}");
+    }
+}
+
+fn collect_statuses(previous: &[DayStatus]) -> Vec<DayStatus> {
+    let timings = Timings::read_from_file();
+    let stars = read_stars();
+
+    all_days()
+        .map(|day| {
+            let mut status = DayStatus::collect(day, &timings, &stars);
+            // test results are only known for this session, keep them across refreshes.
+            if let Some(prev) = previous.iter().find(|s| s.day == day) {
+                status.examples = prev.examples;
+            }
+            status
+        })
+        .collect()
+}
+
+pub fn handle() {
+    let mut stdin = io::stdin();
+    let mut statuses = collect_statuses(&[]);
+    let mut selected = 0;
+
+    loop {
+        let key = {
+            let _raw = RawMode::enable();
+            // raw mode disables the translation of `\n` to `\r\n`.
+            print!(
+                "{ANSI_CLEAR_SCREEN}{}",
+                render(&statuses, selected).replace('\n', "\r\n")
+            );
+            let _ = io::stdout().flush();
+            read_key(&mut stdin)
+        };
+
+        let day = statuses[selected].day;
+
+        match key {
+            None | Some(Key::Char(b'q' | 3)) => break,
+            Some(Key::Up | Key::Char(b'k')) => {
+                selected = selected.checked_sub(COLUMNS).unwrap_or(selected);
+            }
+            Some(Key::Down | Key::Char(b'j')) if selected + COLUMNS < statuses.len() => {
+                selected += COLUMNS;
+            }
+            Some(Key::Left | Key::Char(b'h')) => selected = selected.saturating_sub(1),
+            Some(Key::Right | Key::Char(b'l')) => {
+                selected = (selected + 1).min(statuses.len() - 1);
+            }
+            Some(Key::Char(b'r')) => statuses = collect_statuses(&statuses),
+            Some(Key::Char(action @ (b's' | b't' | b'b' | b'o'))) => {
+                print!("{ANSI_CLEAR_SCREEN}");
+                match action {
+                    b's' => solve::handle(day, true, false, None, false, false),
+                    b't' => statuses[selected].examples = run_tests(day),
+                    b'b' => time::handle(Some(day), false, true),
+                    _ => open_puzzle(day),
+                }
+                wait_for_key(&mut stdin);
+                statuses = collect_statuses(&statuses);
+            }
+            Some(_) => {}
+        }
+    }
+
+    print!("{ANSI_CLEAR_SCREEN}");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_stars, render, DayStatus, ExampleStatus};
+    use crate::{day, template::all_days};
+
+    #[test]
+    fn parses_stars_table() {
+        let readme = [
+            "<!--- advent_readme_stars table --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+        ]
+        .join("\n");
+
+        let stars = parse_stars(&readme);
+        assert_eq!(stars.get(&day!(1)), Some(&2));
+        assert_eq!(stars.get(&day!(2)), Some(&1));
+        assert_eq!(stars.get(&day!(3)), None);
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let statuses: Vec<DayStatus> = all_days()
+            .map(|day| DayStatus {
+                day,
+                scaffolded: false,
+                has_input: false,
+                examples: ExampleStatus::Unknown,
+                stars: 0,
+                total_nanos: None,
+            })
+            .collect();
+
+        let rendered = render(&statuses, 0);
+        // title + 5 rows of 3 lines + 6 borders + 2 lines of help.
+        assert_eq!(rendered.lines().count(), 1 + 5 * 3 + 6 + 2);
+        assert!(rendered.contains("Day 01"));
+        assert!(rendered.contains("Day 25"));
+    }
+}
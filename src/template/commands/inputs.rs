@@ -0,0 +1,96 @@
+use std::{fs, process, str::FromStr};
+
+use crate::template::{all_days, vault, Day};
+
+pub enum Action {
+    Encrypt,
+    Decrypt,
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "encrypt" => Ok(Action::Encrypt),
+            "decrypt" => Ok(Action::Decrypt),
+            _ => Err(format!(
+                "unknown action \"{s}\", expecting \"encrypt\" or \"decrypt\"."
+            )),
+        }
+    }
+}
+
+fn input_path(day: Day) -> String {
+    format!("data/inputs/{day}.txt")
+}
+
+/// Encrypts the input of `day` if it is present and not already stored with identical content.
+fn encrypt(day: Day, passphrase: &str) -> Result<(), vault::VaultError> {
+    let Ok(plaintext) = fs::read_to_string(input_path(day)) else {
+        return Ok(());
+    };
+    if plaintext.is_empty() {
+        return Ok(());
+    }
+
+    let path = vault::blob_path(day);
+
+    // encryption is not deterministic, skip unchanged inputs to avoid churn in the repository.
+    let stored = fs::read(&path)
+        .ok()
+        .and_then(|blob| vault::decrypt(&blob, passphrase).ok());
+    if stored.as_deref() == Some(plaintext.as_str()) {
+        println!("Day {day}: unchanged.");
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, vault::encrypt(&plaintext, passphrase)?)?;
+    println!("Day {day}: encrypted to \"{}\".", path.display());
+    Ok(())
+}
+
+/// Decrypts the stored input of `day`, unless a non-empty plaintext input already exists.
+fn decrypt(day: Day, passphrase: &str) -> Result<(), vault::VaultError> {
+    let path = vault::blob_path(day);
+    let Ok(blob) = fs::read(&path) else {
+        return Ok(());
+    };
+
+    let target = input_path(day);
+    if fs::read_to_string(&target).is_ok_and(|s| !s.is_empty()) {
+        println!("Day {day}: kept existing \"{target}\".");
+        return Ok(());
+    }
+
+    fs::write(&target, vault::decrypt(&blob, passphrase)?)?;
+    println!("Day {day}: decrypted to \"{target}\".");
+    Ok(())
+}
+
+pub fn handle(action: Action, day: Option<Day>) {
+    let passphrase = match vault::read_passphrase() {
+        Ok(passphrase) => passphrase,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    for day in days {
+        let result = match action {
+            Action::Encrypt => encrypt(day, &passphrase),
+            Action::Decrypt => decrypt(day, &passphrase),
+        };
+
+        if let Err(e) = result {
+            eprintln!("Day {day}: {e}");
+            process::exit(1);
+        }
+    }
+}
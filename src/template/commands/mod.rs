@@ -1,5 +1,7 @@
 pub mod all;
+pub mod dashboard;
 pub mod download;
+pub mod inputs;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
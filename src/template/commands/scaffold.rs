@@ -1,25 +1,185 @@
 use std::{
-    fs::{File, OpenOptions},
+    fmt::Display,
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::{Path, PathBuf},
     process,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::template::Day;
+use crate::template::{aoc_cli, Day, ANSI_GREEN, ANSI_RED, ANSI_RESET};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
+const TEMPLATES_DIR: &str = "templates";
+const BACKUP_DIR: &str = "data/backups";
+
+/// Values that get substituted into a module template.
+#[derive(Debug, Default)]
+pub struct Placeholders {
+    pub day: Option<Day>,
+    pub year: Option<u16>,
+    pub title: Option<String>,
+    pub example_part_one: Option<String>,
+    pub example_part_two: Option<String>,
+}
+
+impl Placeholders {
+    /// Replaces all supported `%PLACEHOLDER%` markers in `template`.
+    /// Trailing whitespace left behind by empty placeholders is stripped.
+    pub fn render(&self, template: &str) -> String {
+        let day_number = self.day.map(|d| d.into_inner().to_string());
+        let day_padded = self.day.map(|d| d.to_string());
+        let year = self.year.map(|y| y.to_string());
+
+        let rendered = template
+            .replace("%DAY_NUMBER%", day_number.as_deref().unwrap_or_default())
+            .replace("%DAY_PADDED%", day_padded.as_deref().unwrap_or_default())
+            .replace("%YEAR%", year.as_deref().unwrap_or_default())
+            .replace("%TITLE%", self.title.as_deref().unwrap_or_default())
+            .replace(
+                "%EXAMPLE_PART_ONE%",
+                &format_example(self.example_part_one.as_deref()),
+            )
+            .replace(
+                "%EXAMPLE_PART_TWO%",
+                &format_example(self.example_part_two.as_deref()),
+            );
+
+        let mut lines: Vec<&str> = rendered.lines().map(str::trim_end).collect();
+        if rendered.ends_with('\n') {
+            lines.push("");
+        }
+        lines.join("\n")
+    }
+}
+
+fn format_example(value: Option<&str>) -> String {
+    value.map_or_else(|| "None".into(), |v| format!("Some({v})"))
+}
+
+/// Extracts the puzzle title from a description downloaded by aoc-cli,
+/// i.e. `Historian Hysteria` from `## --- Day 1: Historian Hysteria ---`.
+pub fn parse_title(puzzle: &str) -> Option<String> {
+    let line = puzzle.lines().find(|l| l.contains("--- Day "))?;
+    let title = line.split_once(':')?.1.trim().trim_end_matches('-').trim();
+
+    if title.is_empty() {
+        None
     } else {
-        file.create_new(true);
+        Some(title.to_string())
+    }
+}
+
+fn read_title(day: Day) -> Option<String> {
+    fs::read_to_string(format!("data/puzzles/{day}.md"))
+        .ok()
+        .and_then(|puzzle| parse_title(&puzzle))
+}
+
+/// Lists the names of all templates in the `templates/` directory.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|e| {
+                    let name = e.file_name().into_string().ok()?;
+                    name.strip_suffix(".txt").map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+    names
+}
+
+/// Loads a module template by name. `None` or `default` selects the built-in template.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    match name {
+        None | Some("default") => Ok(MODULE_TEMPLATE.to_string()),
+        Some(name) => fs::read_to_string(format!("{TEMPLATES_DIR}/{name}.txt")).map_err(|_| {
+            let available = available_templates();
+            if available.is_empty() {
+                format!("template \"{name}\" not found, \"{TEMPLATES_DIR}/\" has no templates.")
+            } else {
+                format!(
+                    "template \"{name}\" not found. Available templates: default, {}.",
+                    available.join(", ")
+                )
+            }
+        }),
+    }
+}
+
+/// Options for the `scaffold` command.
+#[derive(Debug, Default)]
+pub struct Options<'a> {
+    /// Replace an existing module file. The previous version is backed up.
+    pub overwrite: bool,
+    /// Clear non-empty input and example files. Previous versions are backed up.
+    pub overwrite_data: bool,
+    /// Download input and puzzle description via aoc-cli.
+    pub download: bool,
+    /// Print what would change without touching any files.
+    pub dry_run: bool,
+    /// Name of a template in `templates/`.
+    pub template: Option<&'a str>,
+    /// Expected example answers for part one and two.
+    pub examples: [Option<String>; 2],
+}
+
+/// Lazily created, timestamped directory that receives copies of files before they are overwritten.
+struct Backup {
+    dir: PathBuf,
+}
+
+impl Backup {
+    fn new() -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            dir: unused_dir(Path::new(BACKUP_DIR), &timestamp.to_string()),
+        }
+    }
+
+    fn target(&self, path: &str) -> PathBuf {
+        self.dir.join(path)
     }
-    file.truncate(true).write(true).open(path)
+
+    /// Copies `path` into the backup directory, keeping its relative path.
+    fn store(&self, path: &str) -> Result<PathBuf, std::io::Error> {
+        let target = self.target(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(path, &target)?;
+        Ok(target)
+    }
+}
+
+/// `root/name`, or `root/name-2`, `root/name-3`, ... if an earlier run within the same second
+/// already backed up files there.
+fn unused_dir(root: &Path, name: &str) -> PathBuf {
+    let mut dir = root.join(name);
+    let mut suffix = 1;
+    while dir.exists() {
+        suffix += 1;
+        dir = root.join(format!("{name}-{suffix}"));
+    }
+    dir
+}
+
+fn create_new_file(path: &str) -> Result<File, std::io::Error> {
+    OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn truncate_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,53 +187,388 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+fn exit_with(message: &str, e: impl Display) -> ! {
+    eprintln!("{message}: {e}");
+    process::exit(1);
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Creates an empty data file if it is missing. Existing non-empty files are only cleared
+/// when `overwrite` is set, after backing them up.
+fn prepare_data_file(path: &str, kind: &str, overwrite: bool, dry_run: bool, backup: &Backup) {
+    let existing = fs::read_to_string(path).ok();
+
+    match existing {
+        None if dry_run => println!("Would create empty {kind} file \"{path}\""),
+        None => match create_new_file(path) {
+            Ok(_) => println!("Created empty {kind} file \"{path}\""),
+            Err(e) => exit_with(&format!("Failed to create {kind} file"), e),
+        },
+        Some(content) if content.is_empty() => {
+            println!("Kept existing empty {kind} file \"{path}\"");
         }
-    };
+        Some(_) if !overwrite => {
+            println!("Kept existing {kind} file \"{path}\". Use `--overwrite-data` to clear it.");
+        }
+        Some(_) if dry_run => {
+            println!(
+                "Would back up {kind} file \"{path}\" to \"{}\" and clear it",
+                backup.target(path).display()
+            );
+        }
+        Some(_) => {
+            match backup.store(path) {
+                Ok(target) => println!("Backed up \"{path}\" to \"{}\"", target.display()),
+                Err(e) => exit_with(&format!("Failed to back up {kind} file"), e),
+            }
+            match truncate_file(path) {
+                Ok(_) => println!("Cleared {kind} file \"{path}\""),
+                Err(e) => exit_with(&format!("Failed to clear {kind} file"), e),
+            }
+        }
+    }
+}
+
+/// What `--download` fetches.
+#[derive(Debug, PartialEq, Eq)]
+enum DownloadScope {
+    InputAndPuzzle,
+    PuzzleOnly,
+}
+
+/// Picks what to download, given the input file as it was before `scaffold` touched it.
+/// A non-empty input is only replaced with `--overwrite-data`, which also backs it up.
+fn download_scope(existing_input: Option<&str>, overwrite_data: bool) -> DownloadScope {
+    match existing_input {
+        Some(content) if !content.is_empty() && !overwrite_data => DownloadScope::PuzzleOnly,
+        _ => DownloadScope::InputAndPuzzle,
+    }
+}
+
+/// Downloads the puzzle description and, depending on `scope`, the input. A failed download
+/// doesn't stop the scaffold, the module is then written without the puzzle title.
+fn download(day: Day, scope: &DownloadScope) {
+    let result = aoc_cli::check().and_then(|_| match scope {
+        DownloadScope::InputAndPuzzle => aoc_cli::download(day),
+        DownloadScope::PuzzleOnly => aoc_cli::download_puzzle(day),
+    });
+    if let Err(e) = result {
+        eprintln!("Failed to download day {day}: {e} Continuing without the puzzle title.");
+    }
+}
+
+fn write_module_file(path: &str, contents: &str, options: &Options, backup: &Backup) {
+    let existing = fs::read_to_string(path).ok();
+
+    if existing.as_deref() == Some(contents) {
+        println!("Module file \"{path}\" is unchanged");
+        return;
+    }
+
+    let needs_backup = existing.as_ref().is_some_and(|c| !c.is_empty());
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    if options.dry_run {
+        let verb = if existing.is_some() {
+            "overwrite"
+        } else {
+            "create"
+        };
+        println!("Would {verb} module file \"{path}\":");
+        print!(
+            "{}",
+            format_diff(
+                path,
+                &diff_lines(existing.as_deref().unwrap_or(""), contents)
+            )
+        );
+        if needs_backup {
+            println!(
+                "Would back up \"{path}\" to \"{}\"",
+                backup.target(path).display()
+            );
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        return;
+    }
+
+    if needs_backup {
+        match backup.store(path) {
+            Ok(target) => println!("Backed up \"{path}\" to \"{}\"", target.display()),
+            Err(e) => exit_with("Failed to back up module file", e),
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    match truncate_file(path).and_then(|mut file| file.write_all(contents.as_bytes())) {
+        Ok(()) if existing.is_some() => println!("Overwrote module file \"{path}\""),
+        Ok(()) => println!("Created module file \"{path}\""),
+        Err(e) => exit_with("Failed to write module contents", e),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Computes a line diff between `old` and `new` via their longest common subsequence.
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] is the LCS length of `old[i..]` and `new[j..]`.
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    }
+
+    let mut result = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            result.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new[j]));
+            j += 1;
         }
     }
+    result.extend(old[i..].iter().map(|l| DiffLine::Removed(l)));
+    result.extend(new[j..].iter().map(|l| DiffLine::Added(l)));
+    result
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+/// Formats a diff like `diff -u`, but without line numbers in the hunk headers.
+fn format_diff(path: &str, diff: &[DiffLine]) -> String {
+    const CONTEXT: usize = 3;
+
+    let is_change = |line: &DiffLine| !matches!(line, DiffLine::Same(_));
+    let visible: Vec<bool> = (0..diff.len())
+        .map(|i| {
+            let from = i.saturating_sub(CONTEXT);
+            let to = (i + CONTEXT + 1).min(diff.len());
+            diff[from..to].iter().any(is_change)
+        })
+        .collect();
+
+    let mut out = format!("--- {path}\n+++ {path} (scaffold)\n");
+    let mut in_hunk = false;
+
+    for (line, visible) in diff.iter().zip(visible) {
+        if !visible {
+            in_hunk = false;
+            continue;
+        }
+        if !in_hunk {
+            out.push_str("@@\n");
+            in_hunk = true;
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        let formatted = match line {
+            DiffLine::Same(l) => format!(" {l}\n"),
+            DiffLine::Removed(l) => format!("{ANSI_RED}-{l}{ANSI_RESET}\n"),
+            DiffLine::Added(l) => format!("{ANSI_GREEN}+{l}{ANSI_RESET}\n"),
+        };
+        out.push_str(&formatted);
+    }
+
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn handle(day: Day, options: &Options) {
+    let input_path = format!("data/inputs/{day}.txt");
+    let example_path = format!("data/examples/{day}.txt");
+    let module_path = format!("src/bin/{day}.rs");
+
+    let template = match load_template(options.template) {
+        Ok(template) => template,
+        Err(e) => exit_with("Failed to load template", e),
+    };
+
+    if !options.overwrite && Path::new(&module_path).exists() {
+        eprintln!("Module file \"{module_path}\" already exists. Use `--overwrite` to replace it.");
+        process::exit(1);
+    }
+
+    let backup = Backup::new();
+    let download_scope = download_scope(
+        fs::read_to_string(&input_path).ok().as_deref(),
+        options.overwrite_data,
+    );
+
+    prepare_data_file(
+        &input_path,
+        "input",
+        options.overwrite_data,
+        options.dry_run,
+        &backup,
+    );
+    prepare_data_file(
+        &example_path,
+        "example",
+        options.overwrite_data,
+        options.dry_run,
+        &backup,
+    );
+
+    // download before rendering the module so that the puzzle title is available.
+    if options.download {
+        match (&download_scope, options.dry_run) {
+            (DownloadScope::InputAndPuzzle, true) => {
+                println!("Would download input and puzzle description")
+            }
+            (DownloadScope::PuzzleOnly, true) => {
+                println!("Would download puzzle description, keeping the existing input")
+            }
+            (DownloadScope::PuzzleOnly, false) => {
+                println!("Downloading only the puzzle description, the existing input is kept");
+                download(day, &download_scope);
+            }
+            (DownloadScope::InputAndPuzzle, false) => download(day, &download_scope),
         }
     }
 
+    let [example_part_one, example_part_two] = options.examples.clone();
+    let placeholders = Placeholders {
+        day: Some(day),
+        year: aoc_cli::get_year(),
+        title: read_title(day),
+        example_part_one,
+        example_part_two,
+    };
+
+    write_module_file(
+        &module_path,
+        &placeholders.render(&template),
+        options,
+        &backup,
+    );
+
+    if options.dry_run {
+        return;
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fs;
+
+    use super::{
+        diff_lines, download_scope, format_diff, parse_title, unused_dir, DiffLine, DownloadScope,
+        Placeholders,
+    };
+    use crate::day;
+
+    #[test]
+    fn parses_title() {
+        let puzzle = "\n## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian* is...";
+        assert_eq!(parse_title(puzzle), Some("Historian Hysteria".into()));
+    }
+
+    #[test]
+    fn handles_missing_title() {
+        assert_eq!(parse_title("no title here"), None);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: Some(day!(7)),
+            year: Some(2024),
+            title: Some("Bridge Repair".into()),
+            example_part_one: Some("3749".into()),
+            example_part_two: None,
+        };
+
+        let rendered = placeholders.render(
+            "%DAY_NUMBER% %DAY_PADDED% %YEAR% %TITLE% %EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO%",
+        );
+        assert_eq!(rendered, "7 07 2024 Bridge Repair Some(3749) None");
+    }
+
+    #[test]
+    fn strips_whitespace_of_empty_placeholders() {
+        let placeholders = Placeholders::default();
+        assert_eq!(
+            placeholders.render("//! Day: %TITLE%\nfoo\n"),
+            "//! Day:\nfoo\n"
+        );
+    }
+
+    #[test]
+    fn diffs_changed_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("x"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_new_file() {
+        let diff = diff_lines("", "a\nb");
+        assert_eq!(diff, vec![DiffLine::Added("a"), DiffLine::Added("b")]);
+    }
+
+    #[test]
+    fn formats_diff_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\n5\n6\n7\n8\nX\n";
+        let formatted = format_diff("a.rs", &diff_lines(old, new));
+        assert!(formatted.starts_with("--- a.rs\n+++ a.rs (scaffold)\n@@\n 6\n"));
+        assert!(!formatted.contains(" 5\n"));
+        assert!(formatted.contains("-9"));
+        assert!(formatted.contains("+X"));
+    }
+
+    #[test]
+    fn picks_unused_backup_dir() {
+        let root = std::env::temp_dir().join(format!("aoc-backups-{}", std::process::id()));
+        assert_eq!(unused_dir(&root, "100"), root.join("100"));
+
+        fs::create_dir_all(root.join("100")).unwrap();
+        assert_eq!(unused_dir(&root, "100"), root.join("100-2"));
+        fs::create_dir_all(root.join("100-2")).unwrap();
+        assert_eq!(unused_dir(&root, "100"), root.join("100-3"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn keeps_existing_input_when_downloading() {
+        assert_eq!(
+            download_scope(Some("1 2\n"), false),
+            DownloadScope::PuzzleOnly
+        );
+        assert_eq!(
+            download_scope(Some("1 2\n"), true),
+            DownloadScope::InputAndPuzzle
+        );
+        assert_eq!(
+            download_scope(Some(""), false),
+            DownloadScope::InputAndPuzzle
+        );
+        assert_eq!(download_scope(None, false), DownloadScope::InputAndPuzzle);
+    }
+}
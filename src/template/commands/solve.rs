@@ -1,8 +1,45 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::watch::{Watcher, ANSI_CLEAR_SCREEN};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    watch: bool,
+    test: bool,
+) {
+    if watch && submit_part.is_some() {
+        eprintln!("`--submit` can not be combined with `--watch`.");
+        process::exit(1);
+    }
+
+    let cmd_args = if test {
+        build_test_args(day, release)
+    } else {
+        build_run_args(day, release, dhat, submit_part)
+    };
+
+    if !watch {
+        run(&cmd_args);
+        return;
+    }
+
+    let mut watcher = Watcher::new(day);
+
+    loop {
+        print!("{ANSI_CLEAR_SCREEN}");
+        println!("{ANSI_BOLD}cargo {}{ANSI_RESET}", cmd_args.join(" "));
+        run(&cmd_args);
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes... (ctrl+c to exit){ANSI_RESET}");
+        watcher.wait_for_change();
+    }
+}
+
+fn build_run_args(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) -> Vec<String> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,8 +60,22 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args
+}
+
+fn build_test_args(day: Day, release: bool) -> Vec<String> {
+    let mut cmd_args = vec!["test".to_string(), "--bin".to_string(), day.to_string()];
+
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args
+}
+
+fn run(cmd_args: &[String]) {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
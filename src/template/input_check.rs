@@ -0,0 +1,119 @@
+/// Sanity checks for puzzle inputs, run before a solution is executed.
+/// Catches inputs that would otherwise silently produce nonsense answers.
+use std::{env, fmt::Display, process};
+
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// A problem found in an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputIssue {
+    /// The file has no content, e.g. right after `scaffold`.
+    Empty,
+    /// The file contains an HTML page, e.g. an error page saved by a failed download.
+    Html,
+    /// The file uses `\r\n` line endings.
+    CrlfLineEndings,
+    /// The file does not end with `\n`. Real inputs always do, so this hints at truncation.
+    MissingTrailingNewline,
+}
+
+impl InputIssue {
+    /// Whether the issue makes the input unusable. Solutions on such inputs are never submitted.
+    pub fn is_fatal(self) -> bool {
+        matches!(self, InputIssue::Empty | InputIssue::Html)
+    }
+}
+
+impl Display for InputIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputIssue::Empty => write!(f, "input file is empty."),
+            InputIssue::Html => write!(
+                f,
+                "input file contains an HTML page, the download probably failed."
+            ),
+            InputIssue::CrlfLineEndings => write!(
+                f,
+                "input file has CRLF (\\r\\n) line endings, parsers splitting on '\\n' will see trailing '\\r'."
+            ),
+            InputIssue::MissingTrailingNewline => write!(
+                f,
+                "input file does not end with a newline, it might be truncated."
+            ),
+        }
+    }
+}
+
+/// Returns all issues found in `input`.
+pub fn check(input: &str) -> Vec<InputIssue> {
+    if input.trim().is_empty() {
+        return vec![InputIssue::Empty];
+    }
+
+    let mut issues = vec![];
+
+    let start: String = input.trim_start().chars().take(16).collect();
+    let start = start.to_ascii_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        issues.push(InputIssue::Html);
+    }
+
+    if input.contains("\r\n") {
+        issues.push(InputIssue::CrlfLineEndings);
+    }
+
+    if !input.ends_with('\n') {
+        issues.push(InputIssue::MissingTrailingNewline);
+    }
+
+    issues
+}
+
+/// Prints a warning for every issue in the input of `day`.
+/// Exits if a fatal issue is found while `--submit` is passed, so nonsense answers are never submitted.
+pub fn report(input: &str, day: Day) {
+    let issues = check(input);
+
+    for issue in &issues {
+        eprintln!("{ANSI_BOLD}Warning:{ANSI_RESET} data/inputs/{day}.txt: {issue}");
+    }
+
+    if issues.iter().any(|i| i.is_fatal()) && env::args().any(|x| x == "--submit") {
+        eprintln!("Refusing to submit a result for a broken input. Fix the input file or run without `--submit`.");
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, InputIssue};
+
+    #[test]
+    fn accepts_valid_input() {
+        assert_eq!(check("3   4\n4   3\n"), vec![]);
+    }
+
+    #[test]
+    fn detects_empty_input() {
+        assert_eq!(check(""), vec![InputIssue::Empty]);
+        assert_eq!(check("\n\n"), vec![InputIssue::Empty]);
+    }
+
+    #[test]
+    fn detects_html() {
+        let issues = check("<!DOCTYPE html>\n<html lang=\"en-us\">\n<head>\n");
+        assert_eq!(issues, vec![InputIssue::Html]);
+        assert!(issues[0].is_fatal());
+    }
+
+    #[test]
+    fn detects_line_ending_issues() {
+        assert_eq!(
+            check("1 2\r\n3 4"),
+            vec![
+                InputIssue::CrlfLineEndings,
+                InputIssue::MissingTrailingNewline
+            ]
+        );
+    }
+}
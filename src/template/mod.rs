@@ -2,7 +2,9 @@ use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod input_check;
 pub mod runner;
+pub mod vault;
 
 pub use day::*;
 
@@ -10,18 +12,37 @@ mod day;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 /// Helper function that reads a text file to a string.
+///
+/// Missing or empty inputs are decrypted from the [`vault`] on the fly, if they have been stored there.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let content = fs::read_to_string(filepath);
+    if folder != "inputs" || !vault::blob_path(day).exists() {
+        return content.unwrap_or_else(|e| panic!("could not open input file: {e:?}"));
+    }
+
+    match content {
+        Ok(f) if !f.is_empty() => f,
+        // `scaffold` creates an empty input file, which must not hide the input stored in the vault.
+        result => vault::read_input(day).unwrap_or_else(|vault_err| {
+            let reason = match result {
+                Ok(_) => "input file is empty".to_string(),
+                Err(e) => format!("could not open input file: {e}"),
+            };
+            panic!("{reason}, and could not decrypt it from the vault: {vault_err}")
+        }),
+    }
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -39,6 +60,8 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Parts can return either `Option<T>` or `Result<T, E>` where `E: Display`, see [`runner::PartResult`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -62,6 +85,7 @@ macro_rules! solution {
         fn main() {
             use $crate::template::runner::*;
             let input = $crate::template::read_file("inputs", DAY);
+            $crate::template::input_check::report(&input, DAY);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
@@ -34,6 +34,25 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             }
         });
 
+    let failures: Vec<String> = timings
+        .iter()
+        .flat_map(|t| {
+            [(1, &t.part_1_error), (2, &t.part_2_error)]
+                .into_iter()
+                .filter_map(move |(part, e)| {
+                    e.as_ref()
+                        .map(|e| format!("Day {} part {part}: {e}", t.day))
+                })
+        })
+        .collect();
+
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        for failure in failures {
+            println!("{failure}");
+        }
+    }
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
@@ -68,7 +87,7 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{runner::FAILURE_PREFIX, Day};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -134,12 +153,22 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_1_error: None,
+            part_2_error: None,
         };
 
+        for (part, e) in output.iter().filter_map(|l| parse_failure(l)) {
+            if part.contains("Part 1") {
+                timings.part_1_error = Some(e.into());
+            } else if part.contains("Part 2") {
+                timings.part_2_error = Some(e.into());
+            }
+        }
+
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples)") || parse_failure(l).is_some() {
                     return None;
                 }
 
@@ -164,6 +193,15 @@ pub mod child_commands {
         timings
     }
 
+    /// Parses lines like `Part 1: ✖ Error: <message>` into the part and the message.
+    fn parse_failure(line: &str) -> Option<(&str, &str)> {
+        // the runner prints an intermediate result first and overwrites it after a `\r`.
+        let line = line.rsplit('\r').next()?;
+        let (part, result) = line.split_once(": ✖ ")?;
+        let message = result.strip_prefix(FAILURE_PREFIX)?;
+        Some((part, message.trim()))
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -174,7 +212,7 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -253,5 +291,40 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_failed_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: ✖ Error: parse Error: bad > benching\rPart 1: ✖ Error: parse Error: bad"
+                        .into(),
+                    "Part 2: 10 (100ms @ 1 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 100000000_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_1_error.unwrap(), "parse Error: bad");
+            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_2_error.is_none(), true);
+        }
+
+        #[test]
+        fn parses_failed_parts_without_intermediate_output() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖ Error: line 3, column 1: Error: x (at \"x\")".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_error.is_none(), true);
+            assert_eq!(
+                res.part_2_error.unwrap(),
+                "line 3, column 1: Error: x (at \"x\")"
+            );
+        }
     }
 }
@@ -9,15 +9,58 @@ use std::{cmp, env, process};
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Marks the error message of a failed part in the output, e.g. `Part 1: ✖ Error: bad input`.
+pub const FAILURE_PREFIX: &str = "Error: ";
+
+/// The outcome of running a solution part.
+pub enum Outcome<'a, T> {
+    Solved(&'a T),
+    /// The part returned `None`.
+    Unsolved,
+    /// The part returned an `Err`, holds its message.
+    Failed(String),
+}
+
+/// Return types accepted for solution parts: `Option<T>` and `Result<T, E>`.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn outcome(&self) -> Outcome<'_, Self::Answer>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn outcome(&self) -> Outcome<'_, T> {
+        match self {
+            Some(answer) => Outcome::Solved(answer),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn outcome(&self) -> Outcome<'_, T> {
+        match self {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(e) => Outcome::Failed(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, R: PartResult>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples) = run_timed(func, input, |result| {
+        print_result(&result.outcome(), &part_str, "");
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let outcome = result.outcome();
+    print_result(&outcome, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
+    if let Outcome::Solved(result) = outcome {
         submit_result(result, day, part);
     }
 }
@@ -94,11 +137,11 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result<T: Display>(result: &Outcome<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Outcome::Solved(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -118,7 +161,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,6 +169,16 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(e) => {
+            // keep the error on a single line, `run_multi` parses it from the output.
+            let e = e.replace('\n', " ");
+            if is_intermediate_result {
+                print!("{part}: ✖ {FAILURE_PREFIX}{e}");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {FAILURE_PREFIX}{e}");
+            }
+        }
     }
 }
 
@@ -133,7 +186,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
 fn submit_result<T: Display>(
-    result: T,
+    result: &T,
     day: Day,
     part: u8,
 ) -> Option<Result<Output, aoc_cli::AocCommandError>> {
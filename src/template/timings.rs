@@ -12,6 +12,9 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Error messages of parts that returned an `Err`.
+    pub part_1_error: Option<String>,
+    pub part_2_error: Option<String>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +54,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -133,6 +136,14 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(e) = &value.part_1_error {
+            map.insert("part_1_error".into(), JsonValue::String(e.clone()));
+        }
+
+        if let Some(e) = &value.part_2_error {
+            map.insert("part_2_error".into(), JsonValue::String(e.clone()));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +177,17 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // optional, timings stored before errors were tracked do not have these keys.
+        let part_1_error = json.get("part_1_error").and_then(|v| v.get::<String>());
+        let part_2_error = json.get("part_2_error").and_then(|v| v.get::<String>());
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_error: part_1_error.cloned(),
+            part_2_error: part_2_error.cloned(),
         })
     }
 }
@@ -191,18 +208,24 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_error: None,
+                    part_2_error: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_error: None,
+                    part_2_error: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_error: None,
+                    part_2_error: None,
                 },
             ],
         }
@@ -221,6 +244,16 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_error, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_errors() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "part_1_error": "bad input", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_error, Some("bad input".to_string()));
+            assert_eq!(timing.part_2_error, None);
         }
 
         #[test]
@@ -282,10 +315,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_error: None,
+                    part_2_error: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +331,12 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_error: None,
+                    part_2_error: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,10 +347,12 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_error: None,
+                    part_2_error: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -334,6 +373,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_error: None,
+                    part_2_error: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +395,8 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_error: None,
+                    part_2_error: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,169 @@
+/// Encrypted storage for puzzle inputs, so they can be committed without publishing them.
+///
+/// Every input is stored as `data/vault/<day>.bin`, encrypted with ChaCha20-Poly1305.
+/// The key is derived from a local passphrase with Argon2, using a random salt per file.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+use crate::template::Day;
+
+/// Environment variable that takes precedence over the passphrase file, e.g. for CI.
+pub const PASSPHRASE_ENV: &str = "AOC_VAULT_PASSPHRASE";
+pub const PASSPHRASE_FILE: &str = ".vault-passphrase";
+
+const VAULT_DIR: &str = "data/vault";
+const MAGIC: &[u8; 4] = b"AOC1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + NONCE_LEN;
+
+#[derive(Debug)]
+pub enum VaultError {
+    MissingPassphrase,
+    MissingBlob(PathBuf),
+    Malformed,
+    Decryption,
+    Crypto(String),
+    IO(io::Error),
+}
+
+impl Display for VaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultError::MissingPassphrase => write!(
+                f,
+                "no vault passphrase found. Set {PASSPHRASE_ENV} or create \"{PASSPHRASE_FILE}\"."
+            ),
+            VaultError::MissingBlob(path) => {
+                write!(f, "no encrypted input at \"{}\".", path.display())
+            }
+            VaultError::Malformed => write!(f, "encrypted input is malformed."),
+            VaultError::Decryption => write!(
+                f,
+                "could not decrypt input, the passphrase is probably wrong."
+            ),
+            VaultError::Crypto(e) => write!(f, "encryption failed: {e}"),
+            VaultError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for VaultError {
+    fn from(e: io::Error) -> Self {
+        VaultError::IO(e)
+    }
+}
+
+#[must_use]
+pub fn blob_path(day: Day) -> PathBuf {
+    Path::new(VAULT_DIR).join(format!("{day}.bin"))
+}
+
+/// Reads the passphrase from [`PASSPHRASE_ENV`] or, if unset, from [`PASSPHRASE_FILE`].
+pub fn read_passphrase() -> Result<String, VaultError> {
+    let passphrase = match env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) => passphrase,
+        Err(_) => fs::read_to_string(PASSPHRASE_FILE).map_err(|_| VaultError::MissingPassphrase)?,
+    };
+
+    let passphrase = passphrase.trim();
+    if passphrase.is_empty() {
+        return Err(VaultError::MissingPassphrase);
+    }
+    Ok(passphrase.to_string())
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key, VaultError> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| VaultError::Crypto(e.to_string()))?;
+    Ok(key)
+}
+
+/// Encrypts `plaintext` into a self-contained blob: magic, salt, nonce and ciphertext.
+pub fn encrypt(plaintext: &str, passphrase: &str) -> Result<Vec<u8>, VaultError> {
+    let salt: [u8; SALT_LEN] = rand_bytes()?;
+    let key = derive_key(passphrase, &salt)?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = ChaCha20Poly1305::new(&key)
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|e| VaultError::Crypto(e.to_string()))?;
+
+    let mut blob = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    blob.extend_from_slice(MAGIC);
+    blob.extend_from_slice(&salt);
+    blob.extend_from_slice(&nonce);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+/// Decrypts a blob created by [`encrypt`].
+pub fn decrypt(blob: &[u8], passphrase: &str) -> Result<String, VaultError> {
+    if blob.len() < HEADER_LEN || !blob.starts_with(MAGIC) {
+        return Err(VaultError::Malformed);
+    }
+
+    let (salt, rest) = blob[MAGIC.len()..].split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt)?;
+    let plaintext = ChaCha20Poly1305::new(&key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| VaultError::Decryption)?;
+
+    String::from_utf8(plaintext).map_err(|_| VaultError::Malformed)
+}
+
+fn rand_bytes<const N: usize>() -> Result<[u8; N], VaultError> {
+    use chacha20poly1305::aead::rand_core::RngCore;
+
+    let mut bytes = [0u8; N];
+    OsRng
+        .try_fill_bytes(&mut bytes)
+        .map_err(|e| VaultError::Crypto(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// Reads and decrypts the stored input for `day`.
+pub fn read_input(day: Day) -> Result<String, VaultError> {
+    let path = blob_path(day);
+    let blob = fs::read(&path).map_err(|_| VaultError::MissingBlob(path))?;
+    decrypt(&blob, &read_passphrase()?)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decrypt, encrypt, VaultError};
+
+    #[test]
+    fn roundtrips_input() {
+        let blob = encrypt("1 2 3\n", "hunter2").unwrap();
+        assert_eq!(decrypt(&blob, "hunter2").unwrap(), "1 2 3\n");
+    }
+
+    #[test]
+    fn rejects_wrong_passphrase() {
+        let blob = encrypt("1 2 3\n", "hunter2").unwrap();
+        assert!(matches!(
+            decrypt(&blob, "hunter3"),
+            Err(VaultError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_blob() {
+        assert!(matches!(decrypt(b"AOC1", "x"), Err(VaultError::Malformed)));
+    }
+}
@@ -0,0 +1,121 @@
+/// Polling file watcher used by `solve --watch`.
+/// Uses modification times only, so it works without any OS-specific notification API.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::Day;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+pub const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+type Snapshot = HashMap<PathBuf, SystemTime>;
+
+pub struct Watcher {
+    day: Day,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            snapshot: snapshot(day),
+        }
+    }
+
+    /// Blocks until one of the watched files is created, modified or removed.
+    pub fn wait_for_change(&mut self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if snapshot(self.day) != self.snapshot {
+                // editors often write files in several steps, wait for them to settle.
+                thread::sleep(DEBOUNCE);
+                self.snapshot = snapshot(self.day);
+                return;
+            }
+        }
+    }
+}
+
+/// Files relevant to a day: its solution, the library sources (including `src/template/`)
+/// and its input and example files.
+fn is_watched(path: &Path, day: Day) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+
+    let parent = path.parent().unwrap_or(Path::new(""));
+
+    if parent.ends_with("src/bin") {
+        name == format!("{day}.rs")
+    } else if parent.ends_with("data/inputs") || parent.ends_with("data/examples") {
+        // matches `08.txt` as well as part files like `08-2.txt`.
+        name.strip_prefix(&day.to_string())
+            .is_some_and(|rest| rest == ".txt" || rest.starts_with('-'))
+    } else {
+        path.starts_with("src")
+    }
+}
+
+fn snapshot(day: Day) -> Snapshot {
+    let mut files = Snapshot::new();
+    for root in ["src", "data/inputs", "data/examples"] {
+        collect(Path::new(root), day, &mut files);
+    }
+    files
+}
+
+fn collect(dir: &Path, day: Day, files: &mut Snapshot) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            collect(&path, day, files);
+        } else if is_watched(&path, day) {
+            if let Ok(modified) = metadata.modified() {
+                files.insert(path, modified);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_watched;
+    use crate::day;
+    use std::path::Path;
+
+    #[test]
+    fn watches_relevant_files() {
+        let day = day!(8);
+        assert!(is_watched(Path::new("src/bin/08.rs"), day));
+        assert!(is_watched(Path::new("src/lib.rs"), day));
+        assert!(is_watched(Path::new("src/template/runner.rs"), day));
+        assert!(is_watched(Path::new("data/inputs/08.txt"), day));
+        assert!(is_watched(Path::new("data/examples/08.txt"), day));
+        assert!(is_watched(Path::new("data/examples/08-2.txt"), day));
+    }
+
+    #[test]
+    fn ignores_other_days() {
+        let day = day!(8);
+        assert!(!is_watched(Path::new("src/bin/09.rs"), day));
+        assert!(!is_watched(Path::new("data/inputs/09.txt"), day));
+        assert!(!is_watched(Path::new("data/examples/081.txt"), day));
+        assert!(!is_watched(Path::new("data/timings.json"), day));
+    }
+}
@@ -0,0 +1,52 @@
+//! Helpers for tests that generate their own inputs.
+
+/// A tiny seeded xorshift64 generator, so generated inputs are the same on every run.
+///
+/// Not suitable for anything but tests: the output is easy to predict and only as random as
+/// differential tests need it to be.
+#[derive(Debug, Clone)]
+pub struct XorShift {
+    state: u64,
+}
+
+impl XorShift {
+    /// # Panics
+    /// If `seed` is `0`, which would only ever produce zeroes.
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "xorshift needs a non-zero seed");
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A value in `0..n`. Slightly biased towards small values, which doesn't matter for tests.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut a = XorShift::new(42);
+        let mut b = XorShift::new(42);
+        let a: Vec<u64> = (0..5).map(|_| a.next_u64()).collect();
+        let b: Vec<u64> = (0..5).map(|_| b.next_u64()).collect();
+        assert_eq!(a, b);
+        assert_ne!(a[0], a[1]);
+    }
+
+    #[test]
+    fn test_below() {
+        let mut rng = XorShift::new(7);
+        assert!((0..1000).all(|_| rng.below(10) < 10));
+    }
+}